@@ -0,0 +1,101 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::fs;
+
+use graph_process_manager_core::process::config::AbstractProcessConfiguration;
+use graph_process_manager_core::process::manager::GenericProcessManager;
+
+use crate::aut::logger::GenericAutLogger;
+use crate::control::{LoggerControl, TerminationReason};
+use crate::error::LoggerError;
+use crate::logger::{AbstractProcessLogger, LoggerResult};
+use crate::meta::EventMeta;
+use crate::util::create_folder;
+
+
+impl<Conf: AbstractProcessConfiguration> GenericAutLogger<Conf> {
+
+    fn get_aut_state_id(&self, node_id: u32) -> Result<usize, LoggerError> {
+        self.explo_node_id_to_aut_state_id_map
+            .get(&node_id)
+            .copied()
+            .ok_or_else(|| LoggerError::Custom(format!("no LTS state for node {}", node_id)))
+    }
+}
+
+
+impl<Conf: AbstractProcessConfiguration + 'static> AbstractProcessLogger<Conf> for GenericAutLogger<Conf> {
+
+    fn log_new_node(
+        &mut self,
+        _context_and_param: &Conf::ContextAndParameterization,
+        _meta: &EventMeta<Conf>,
+        new_node_id: u32,
+        _new_node: &Conf::DomainSpecificNode,
+    ) -> LoggerResult {
+        self.explo_node_id_to_aut_state_id_map.insert(new_node_id, self.next_aut_state_id);
+        self.next_aut_state_id += 1;
+        Ok(LoggerControl::Continue)
+    }
+
+    fn log_new_step(
+        &mut self,
+        context_and_param: &Conf::ContextAndParameterization,
+        _meta: &EventMeta<Conf>,
+        origin_node_id: u32,
+        step: &Conf::DomainSpecificStep,
+        target_node_id: u32,
+        _target_node: &Conf::DomainSpecificNode,
+    ) -> LoggerResult {
+        let aut_orig = self.get_aut_state_id(origin_node_id)?;
+        let aut_targ = self.get_aut_state_id(target_node_id)?;
+        let label = self.labeller.step_label(context_and_param, step);
+        self.transitions.push((aut_orig, label, aut_targ));
+        Ok(LoggerControl::Continue)
+    }
+
+    fn log_filtered(
+        &mut self,
+        context_and_param: &Conf::ContextAndParameterization,
+        _meta: &EventMeta<Conf>,
+        parent_node_id: u32,
+        filtration_result: &Conf::FiltrationResult,
+    ) -> LoggerResult {
+        if self.filtrations_to_sink {
+            let aut_orig = self.get_aut_state_id(parent_node_id)?;
+            let sink = *self.sink_state_id.get_or_insert_with(|| {
+                self.next_aut_state_id += 1;
+                self.next_aut_state_id - 1
+            });
+            let label = self.labeller.filtration_label(context_and_param, filtration_result);
+            self.transitions.push((aut_orig, label, sink));
+        }
+        Ok(LoggerControl::Continue)
+    }
+
+    fn log_terminate_process(
+        &mut self,
+        _manager: &GenericProcessManager<Conf>,
+        _reason: &TerminationReason,
+    ) -> LoggerResult {
+        create_folder(&self.parent_folder)?;
+        let path = self.get_output_path();
+        fs::write(&path, self.to_aut())
+            .map_err(|e| LoggerError::io(format!("writing '{}'", path.display()), e))?;
+        Ok(LoggerControl::Continue)
+    }
+}
@@ -0,0 +1,44 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use graph_process_manager_core::process::config::AbstractProcessConfiguration;
+
+
+/**
+ * Gives the action labels of the transitions of the Aldebaran LTS.
+ * Internal actions should be labelled as the target toolset expects (e.g. "tau" for mCRL2, "i" for CADP).
+ * Double quotes cannot appear in .aut labels and are replaced with single quotes,
+ * and line breaks, which would end the transition, are replaced with spaces.
+ **/
+pub trait AutProcessLabeller<Conf : AbstractProcessConfiguration> {
+
+    fn step_label(
+        &self,
+        context_and_param: &Conf::ContextAndParameterization,
+        step : &Conf::DomainSpecificStep
+    ) -> String;
+
+    /// Label of the transition towards the sink state,
+    /// when filtered branches are kept (see GenericAutLogger::new).
+    fn filtration_label(
+        &self,
+        _context_and_param: &Conf::ContextAndParameterization,
+        _filtration_result : &Conf::FiltrationResult
+    ) -> String {
+        "filtered".to_string()
+    }
+
+}
@@ -0,0 +1,93 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use graph_process_manager_core::process::config::AbstractProcessConfiguration;
+
+use crate::aut::labeller::AutProcessLabeller;
+use crate::logger_registry::LoggerWithProduct;
+
+
+/**
+ * Logger that writes the explored state space as a labelled transition system
+ * in the Aldebaran format "{name}.aut" (read by mCRL2, CADP...) when the process terminates.
+ *
+ * The initial state is that of the initial node.
+ * If filtrations_to_sink is set, each filtration becomes a transition
+ * from the filtered node to a single sink state.
+ **/
+pub struct GenericAutLogger<Conf : AbstractProcessConfiguration> {
+    pub labeller : Box<dyn AutProcessLabeller<Conf>>,
+    pub filtrations_to_sink : bool,
+    // ***
+    pub(crate) parent_folder : String,
+    pub(crate) name : String,
+    // ***
+    // depending on the process (filtered nodes, memoization etc,
+    // the set of nodes may not be a contiguous 0..n
+    pub(crate) explo_node_id_to_aut_state_id_map : BTreeMap<u32,usize>,
+    pub(crate) next_aut_state_id : usize,
+    pub(crate) sink_state_id : Option<usize>,
+    pub(crate) transitions : Vec<(usize,String,usize)>
+}
+
+impl<Conf : AbstractProcessConfiguration> GenericAutLogger<Conf> {
+
+    pub fn new(labeller : Box<dyn AutProcessLabeller<Conf>>,
+               filtrations_to_sink : bool,
+               parent_folder : String,
+               name : String) -> Self {
+        Self {
+            labeller,
+            filtrations_to_sink,
+            parent_folder,
+            name,
+            explo_node_id_to_aut_state_id_map : BTreeMap::new(),
+            next_aut_state_id : 0,
+            sink_state_id : None,
+            transitions : vec![]
+        }
+    }
+
+    pub fn get_output_path(&self) -> PathBuf {
+        [&self.parent_folder, &format!("{}.aut", self.name)].iter().collect()
+    }
+
+    /** The Aldebaran document for the events logged so far. **/
+    pub fn to_aut(&self) -> String {
+        let mut aut = format!("des (0, {}, {})\n", self.transitions.len(), self.next_aut_state_id);
+        for (from, label, to) in &self.transitions {
+            let label = label.replace('"', "'").replace("\r\n", " ").replace(['\r', '\n'], " ");
+            let _ = writeln!(aut, "({}, \"{}\", {})", from, label, to);
+        }
+        aut
+    }
+}
+
+
+/**
+ * The product is the Aldebaran document.
+ **/
+impl<Conf : AbstractProcessConfiguration> LoggerWithProduct for GenericAutLogger<Conf> {
+    type Product = String;
+
+    fn into_product(self) -> String {
+        self.to_aut()
+    }
+}
@@ -0,0 +1,68 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::fmt;
+
+
+/**
+ * Signal returned by a logger callback to drive_loggers.
+ *
+ * Variants are ordered by strength: when several loggers answer the same event,
+ * the strongest signal wins.
+ **/
+#[derive(Clone, Copy, PartialEq, Debug, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum LoggerControl {
+    /** Keep exploring. **/
+    #[default]
+    Continue,
+    /**
+     * Keep dispatching events until the node currently being expanded has had
+     * all its children processed (i.e. up to and including its AllChildrenProcessed event),
+     * then stop. When requested on a NodeWithoutChildren event, that leaf is the current node.
+     * This way every node logged so far also has its incoming step logged.
+     **/
+    StopAfterCurrentNode,
+    /** Stop right after the current event. **/
+    Stop,
+}
+
+
+/**
+ * Why the exploration ended, as given to log_terminate_process.
+ **/
+#[derive(Clone, PartialEq, Debug, Eq, Hash, Default)]
+pub enum TerminationReason {
+    /** The process manager has no more events to emit. **/
+    #[default]
+    ExplorationCompleted,
+    /** A logger returned a LoggerControl other than Continue. **/
+    StoppedByLogger {
+        logger_index: usize,
+        logger_name: String,
+        control: LoggerControl,
+    },
+}
+
+impl fmt::Display for TerminationReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TerminationReason::ExplorationCompleted => write!(f, "exploration completed"),
+            TerminationReason::StoppedByLogger { logger_index, logger_name, control } => {
+                write!(f, "stopped by logger #{} ({}) with {:?}", logger_index, logger_name, control)
+            }
+        }
+    }
+}
@@ -0,0 +1,430 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+
+use graph_process_manager_core::process::config::AbstractProcessConfiguration;
+use graph_process_manager_core::process::event::ExplorationEvent;
+use graph_process_manager_core::process::manager::GenericProcessManager;
+
+use crate::control::{LoggerControl, TerminationReason};
+use crate::logger::{AbstractProcessLogger, LoggerResult};
+use crate::meta::{EventClock, EventMeta, EventStamp};
+use crate::registry::{NodeInfo, NodeRegistry, NodeRegistryPolicy};
+use crate::report::{LoggerEventKind, LoggerFailure, LoggerPanic, LoggersRunReport};
+use crate::util::panic_message;
+
+
+/**
+ * Dispatches ExplorationEvents to a set of loggers.
+ *
+ * It is shared by every way of feeding events to loggers (drive_loggers, replay_loggers ...)
+ * so that loggers observe the exact same behavior whatever the source of the events:
+ * - a local id→node registry (bounded by a NodeRegistryPolicy) is maintained so that
+ *   log_new_step can supply target_node,
+ * - an EventMeta is computed for each event; as a NewNode event is emitted right before
+ *   the NewStep leading to that node, it is held back until that NewStep is received
+ *   so as to know the parent of the new node,
+ * - a logger whose callback returns an error (or panics, in supervised mode)
+ *   is disabled for the rest of the run,
+ * - the strongest LoggerControl requested by the loggers is honoured.
+ **/
+pub(crate) struct LoggersDispatcher<Conf, L>
+where
+    Conf: AbstractProcessConfiguration,
+    L: AbstractProcessLogger<Conf> + ?Sized,
+{
+    // None for a logger that was removed, so that the indices of the others do not change
+    loggers: Vec<Option<Box<L>>>,
+    // index of loggers[0] in the whole set of loggers, used in reports
+    first_logger_index: usize,
+    failed: Vec<bool>,
+    report: LoggersRunReport,
+    node_registry: NodeRegistry<Conf::DomainSpecificNode>,
+    clock: EventClock,
+    // NewNode event waiting for the NewStep that leads to that node
+    pending_new_node: Option<(EventStamp, u32, Arc<Conf::DomainSpecificNode>)>,
+    stop_request: Option<(usize, LoggerControl)>,
+    // node being expanded when the stop request was made, for StopAfterCurrentNode
+    stop_node: Option<u32>,
+    // node being expanded, i.e. the subject of the last event (the parent for NewNode)
+    current_node: Option<u32>,
+    // name of the logger that made the stop request, which may since have been removed
+    stop_requester_name: String,
+    finished: bool,
+    // whether panics in logger callbacks are caught
+    supervised: bool,
+}
+
+impl<Conf, L> LoggersDispatcher<Conf, L>
+where
+    Conf: AbstractProcessConfiguration,
+    L: AbstractProcessLogger<Conf> + ?Sized,
+{
+
+    pub fn new(loggers: Vec<Box<L>>, registry_policy: NodeRegistryPolicy) -> Self {
+        Self::new_with_offset(loggers, 0, registry_policy)
+    }
+
+    /**
+     * Dispatcher for a subset of loggers, the first of which is at position first_logger_index
+     * in the whole set of loggers.
+     **/
+    pub fn new_with_offset(
+        loggers: Vec<Box<L>>,
+        first_logger_index: usize,
+        registry_policy: NodeRegistryPolicy,
+    ) -> Self {
+        let failed = vec![false; loggers.len()];
+        Self {
+            loggers: loggers.into_iter().map(Some).collect(),
+            first_logger_index,
+            failed,
+            report: LoggersRunReport::default(),
+            node_registry: NodeRegistry::new(registry_policy),
+            clock: EventClock::new(),
+            pending_new_node: None,
+            stop_request: None,
+            stop_node: None,
+            current_node: None,
+            stop_requester_name: String::new(),
+            finished: false,
+            supervised: false,
+        }
+    }
+
+    /**
+     * In supervised mode, a logger callback that panics is handled like one that returns an error,
+     * except that it is recorded in the panics of the report.
+     **/
+    pub fn with_supervision(mut self, supervised: bool) -> Self {
+        self.supervised = supervised;
+        self
+    }
+
+    /**
+     * Whether more events are expected i.e. no logger has requested to stop.
+     **/
+    pub fn wants_more_events(&self) -> bool {
+        !self.finished
+    }
+
+    /** Number of exploration events handled so far. **/
+    pub fn event_count(&self) -> u64 {
+        self.clock.count()
+    }
+
+    pub fn initialize(&mut self, manager: &GenericProcessManager<Conf>)
+    where
+        Conf: 'static,
+    {
+        self.node_registry.resolve_policy(manager.is_memoized());
+        self.clock = EventClock::new();
+        self.dispatch(LoggerEventKind::InitializeProcess, |logger| logger.log_initialize_process(manager));
+    }
+
+    /**
+     * Adds a logger after the others and calls its log_initialize_process.
+     * Returns its index.
+     **/
+    pub fn add_logger(&mut self, logger: Box<L>, manager: &GenericProcessManager<Conf>) -> usize {
+        self.loggers.push(Some(logger));
+        self.failed.push(false);
+        let local_index = self.loggers.len() - 1;
+        self.dispatch_to(local_index, LoggerEventKind::InitializeProcess,
+            &mut |logger: &mut L| logger.log_initialize_process(manager));
+        self.finish_if_stopped();
+        self.first_logger_index + local_index
+    }
+
+    /**
+     * Removes a logger, which receives no further event (not even log_terminate_process).
+     * The indices of the other loggers are unchanged.
+     **/
+    pub fn remove_logger(&mut self, logger_index: usize) -> Option<Box<L>> {
+        let local_index = logger_index.checked_sub(self.first_logger_index)?;
+        self.loggers.get_mut(local_index)?.take()
+    }
+
+    pub fn handle_event(
+        &mut self,
+        ctx: &Conf::ContextAndParameterization,
+        event: &ExplorationEvent<Conf>,
+    ) {
+        let stamp = self.clock.tick();
+        self.handle_stamped_event(ctx, event, stamp);
+    }
+
+    /**
+     * Handles an event that was stamped when it was emitted,
+     * for events that are not handled as soon as they are emitted.
+     **/
+    pub fn handle_stamped_event(
+        &mut self,
+        ctx: &Conf::ContextAndParameterization,
+        event: &ExplorationEvent<Conf>,
+        stamp: EventStamp,
+    ) {
+        match event {
+            ExplorationEvent::NewNode { id, node } if !self.node_registry.expects_initial_node() => {
+                self.flush_pending_new_node(ctx, None);
+                self.pending_new_node = Some((stamp, *id, Arc::clone(node)));
+            }
+            ExplorationEvent::NewNode { id, node } => {
+                self.dispatch_new_node(ctx, stamp, *id, node, None);
+            }
+            ExplorationEvent::NewStep { origin_node_id, step, target_node_id } => {
+                if self.pending_new_node.as_ref().is_some_and(|(_, id, _)| id == target_node_id) {
+                    self.flush_pending_new_node(ctx, Some(*origin_node_id));
+                    if self.finished {
+                        return;
+                    }
+                } else {
+                    self.flush_pending_new_node(ctx, None);
+                }
+                self.current_node = Some(*origin_node_id);
+                let origin = self.node_registry.get_node(*origin_node_id);
+                if let Some(target) = self.node_registry.on_new_step(*origin_node_id, *target_node_id) {
+                    let meta = self.make_meta(stamp, *target_node_id, origin.as_deref());
+                    let kind = LoggerEventKind::NewStep {
+                        origin_node_id: *origin_node_id,
+                        target_node_id: *target_node_id,
+                    };
+                    self.dispatch(kind, |logger| {
+                        logger.log_new_step(ctx, &meta, *origin_node_id, step, *target_node_id, &target)
+                    });
+                }
+            }
+            ExplorationEvent::AllChildrenProcessed { parent_node_id } => {
+                self.flush_pending_new_node(ctx, None);
+                self.current_node = Some(*parent_node_id);
+                let meta = self.make_meta(stamp, *parent_node_id, None);
+                let kind = LoggerEventKind::AllChildrenProcessed { parent_node_id: *parent_node_id };
+                self.dispatch(kind, |logger| logger.log_all_children_processed(ctx, &meta, *parent_node_id));
+                self.node_registry.on_node_completed(*parent_node_id);
+                self.finish_if_stop_node_completed(*parent_node_id);
+            }
+            ExplorationEvent::NodeWithoutChildren { node_id } => {
+                self.flush_pending_new_node(ctx, None);
+                self.current_node = Some(*node_id);
+                let meta = self.make_meta(stamp, *node_id, None);
+                let kind = LoggerEventKind::NodeWithoutChildren { node_id: *node_id };
+                self.dispatch(kind, |logger| logger.log_notify_node_without_children(ctx, &meta, *node_id));
+                self.node_registry.on_node_completed(*node_id);
+                self.finish_if_stop_node_completed(*node_id);
+            }
+            ExplorationEvent::Filtered { parent_node_id, filtration_result } => {
+                self.flush_pending_new_node(ctx, None);
+                self.current_node = Some(*parent_node_id);
+                let meta = self.make_meta(stamp, *parent_node_id, None);
+                let kind = LoggerEventKind::Filtered { parent_node_id: *parent_node_id };
+                self.dispatch(kind, |logger| logger.log_filtered(ctx, &meta, *parent_node_id, filtration_result));
+            }
+        }
+    }
+
+    /** Dispatches the NewNode event held back, if any. **/
+    fn flush_pending_new_node(
+        &mut self,
+        ctx: &Conf::ContextAndParameterization,
+        parent_node_id: Option<u32>,
+    ) {
+        if let Some((stamp, id, node)) = self.pending_new_node.take() {
+            self.dispatch_new_node(ctx, stamp, id, &node, parent_node_id);
+        }
+    }
+
+    fn dispatch_new_node(
+        &mut self,
+        ctx: &Conf::ContextAndParameterization,
+        stamp: EventStamp,
+        id: u32,
+        node: &Arc<Conf::DomainSpecificNode>,
+        parent_node_id: Option<u32>,
+    ) {
+        if self.node_registry.expects_initial_node() {
+            self.current_node = Some(id);
+        } else if parent_node_id.is_some() {
+            self.current_node = parent_node_id;
+        }
+        let info = self.node_registry.on_new_node(id, node, parent_node_id);
+        let meta = meta_from_info(stamp, Some(info), None);
+        self.dispatch(LoggerEventKind::NewNode { node_id: id },
+            |logger| logger.log_new_node(ctx, &meta, id, node.as_ref()));
+    }
+
+    /**
+     * Ends the run once the node that was being expanded when StopAfterCurrentNode was requested is complete
+     * (or any node, if that node is unknown).
+     **/
+    fn finish_if_stop_node_completed(&mut self, completed_node_id: u32) {
+        if matches!(self.stop_request, Some((_, LoggerControl::StopAfterCurrentNode)))
+            && self.stop_node.is_none_or(|stop_node| stop_node == completed_node_id)
+        {
+            self.finished = true;
+        }
+    }
+
+    fn make_meta<'n>(
+        &self,
+        stamp: EventStamp,
+        subject_node_id: u32,
+        origin_node: Option<&'n Conf::DomainSpecificNode>,
+    ) -> EventMeta<'n, Conf> {
+        meta_from_info(stamp, self.node_registry.get_info(subject_node_id), origin_node)
+    }
+
+    /**
+     * The strongest control other than Continue requested so far,
+     * together with the index of the first logger that requested it.
+     **/
+    pub fn stop_request(&self) -> Option<(usize, LoggerControl)> {
+        self.stop_request
+    }
+
+    /** The node that was being expanded when StopAfterCurrentNode was requested, if known. **/
+    pub fn stop_node(&self) -> Option<u32> {
+        self.stop_node
+    }
+
+    pub fn termination_reason(&self) -> TerminationReason {
+        match self.stop_request {
+            None => TerminationReason::ExplorationCompleted,
+            Some((logger_index, control)) => TerminationReason::StoppedByLogger {
+                logger_index,
+                logger_name: self.stop_requester_name.clone(),
+                control,
+            },
+        }
+    }
+
+    pub fn terminate(self, manager: &GenericProcessManager<Conf>) -> LoggersRunReport {
+        let reason = self.termination_reason();
+        self.terminate_with(manager, reason)
+    }
+
+    pub fn terminate_with(
+        mut self,
+        manager: &GenericProcessManager<Conf>,
+        reason: TerminationReason,
+    ) -> LoggersRunReport {
+        // the events ended right after a NewNode
+        if !self.finished {
+            self.flush_pending_new_node(&manager.context_and_param, None);
+        }
+        self.dispatch(LoggerEventKind::TerminateProcess,
+            |logger| logger.log_terminate_process(manager, &reason));
+        self.report.termination = reason;
+        self.report
+    }
+
+    /**
+     * Calls a given callback on every logger that has not failed yet,
+     * recording failures in the report and keeping track of the strongest
+     * control other than Continue that was requested (and by which logger first).
+     **/
+    fn dispatch<F>(&mut self, event: LoggerEventKind, mut callback: F)
+    where
+        F: FnMut(&mut L) -> LoggerResult,
+    {
+        for local_index in 0..self.loggers.len() {
+            self.dispatch_to(local_index, event, &mut callback);
+        }
+        self.finish_if_stopped();
+    }
+
+    fn dispatch_to<F>(&mut self, local_index: usize, event: LoggerEventKind, callback: &mut F)
+    where
+        F: FnMut(&mut L) -> LoggerResult,
+    {
+        if self.failed[local_index] {
+            return;
+        }
+        let Some(logger) = self.loggers[local_index].as_mut() else { return };
+        let logger_index = self.first_logger_index + local_index;
+        let result = if self.supervised {
+            match panic::catch_unwind(AssertUnwindSafe(|| callback(logger.as_mut()))) {
+                Ok(result) => result,
+                Err(payload) => {
+                    self.failed[local_index] = true;
+                    self.report.panics.push(LoggerPanic {
+                        logger_index,
+                        logger_name: logger.get_logger_name(),
+                        event,
+                        message: panic_message(payload.as_ref()),
+                    });
+                    return;
+                }
+            }
+        } else {
+            callback(logger.as_mut())
+        };
+        match result {
+            Ok(LoggerControl::Continue) => {}
+            Ok(control) => {
+                if self.stop_request.is_none_or(|(_, previous)| previous < control) {
+                    self.stop_request = Some((logger_index, control));
+                    self.stop_node = self.current_node;
+                    self.stop_requester_name = logger.get_logger_name();
+                }
+            }
+            Err(error) => {
+                self.failed[local_index] = true;
+                self.report.failures.push(LoggerFailure {
+                    logger_index,
+                    logger_name: logger.get_logger_name(),
+                    event,
+                    error,
+                });
+            }
+        }
+    }
+
+    fn finish_if_stopped(&mut self) {
+        if matches!(self.stop_request, Some((_, LoggerControl::Stop))) {
+            self.finished = true;
+        }
+    }
+}
+
+/**
+ * Loggers borrowed for the duration of a run, e.g. to give loggers owned by the caller to a dispatcher.
+ **/
+pub(crate) fn borrow_loggers<'b, Conf: AbstractProcessConfiguration>(
+    loggers: &'b mut [Box<dyn AbstractProcessLogger<Conf> + '_>],
+) -> Vec<Box<dyn AbstractProcessLogger<Conf> + 'b>> {
+    loggers
+        .iter_mut()
+        .map(|logger| Box::new(logger.as_mut()) as Box<dyn AbstractProcessLogger<Conf> + 'b>)
+        .collect()
+}
+
+
+fn meta_from_info<Conf: AbstractProcessConfiguration>(
+    stamp: EventStamp,
+    info: Option<NodeInfo>,
+    origin_node: Option<&Conf::DomainSpecificNode>,
+) -> EventMeta<'_, Conf> {
+    EventMeta {
+        event_index: stamp.index,
+        elapsed: stamp.elapsed,
+        depth: info.and_then(|info| info.depth),
+        parent_node_id: info.and_then(|info| info.parent_node_id),
+        discovery_index: info.map(|info| info.discovery_index),
+        origin_node,
+    }
+}
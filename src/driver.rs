@@ -0,0 +1,155 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use graph_process_manager_core::process::config::AbstractProcessConfiguration;
+use graph_process_manager_core::process::event::ExplorationEvent;
+use graph_process_manager_core::process::manager::GenericProcessManager;
+
+use crate::dispatch::LoggersDispatcher;
+use crate::logger::{AbstractProcessLogger, DriveLoggersOptions};
+use crate::report::LoggersRunReport;
+
+
+/**
+ * Drives a set of loggers over a process exploration, one event at a time,
+ * so that the exploration can be interleaved with other code
+ * (progress display, checkpointing, adding or removing loggers...).
+ *
+ * log_initialize_process is called on creation.
+ * log_terminate_process is called by finish, which must be called to get the LoggersRunReport.
+ *
+ * Loggers are identified by their index, which is their position in the initial set of loggers
+ * or the one returned by add_logger. Removing a logger does not change the indices of the others.
+ **/
+pub struct LoggerDriver<'a, Conf: 'static + AbstractProcessConfiguration> {
+    manager: &'a mut GenericProcessManager<Conf>,
+    dispatcher: LoggersDispatcher<Conf, dyn AbstractProcessLogger<Conf> + 'a>,
+    exhausted: bool,
+}
+
+impl<'a, Conf: 'static + AbstractProcessConfiguration> LoggerDriver<'a, Conf> {
+
+    pub fn new(
+        manager: &'a mut GenericProcessManager<Conf>,
+        loggers: Vec<Box<dyn AbstractProcessLogger<Conf> + 'a>>,
+    ) -> Self {
+        Self::with_options(manager, loggers, &DriveLoggersOptions::default())
+    }
+
+    pub fn with_options(
+        manager: &'a mut GenericProcessManager<Conf>,
+        loggers: Vec<Box<dyn AbstractProcessLogger<Conf> + 'a>>,
+        options: &DriveLoggersOptions,
+    ) -> Self {
+        let mut dispatcher = LoggersDispatcher::new(loggers, options.node_registry_policy)
+            .with_supervision(options.supervised);
+        dispatcher.initialize(manager);
+        Self { manager, dispatcher, exhausted: false }
+    }
+
+    pub fn get_manager(&self) -> &GenericProcessManager<Conf> {
+        self.manager
+    }
+
+    /** Number of exploration events handled so far. **/
+    pub fn get_event_count(&self) -> u64 {
+        self.dispatcher.event_count()
+    }
+
+    /**
+     * Whether no more events will be handled,
+     * either because the exploration is over or because a logger requested to stop.
+     **/
+    pub fn is_finished(&self) -> bool {
+        self.exhausted || !self.dispatcher.wants_more_events()
+    }
+
+    /**
+     * Handles the next exploration event and returns it,
+     * or returns None if the driver is finished.
+     * Note that a NewNode event only reaches the loggers along with the NewStep that follows it.
+     **/
+    pub fn step(&mut self) -> Option<ExplorationEvent<Conf>> {
+        if self.is_finished() {
+            return None;
+        }
+        match self.manager.next() {
+            None => {
+                self.exhausted = true;
+                None
+            }
+            Some(event) => {
+                self.dispatcher.handle_event(&self.manager.context_and_param, &event);
+                Some(event)
+            }
+        }
+    }
+
+    /**
+     * Handles events until one that satisfies the predicate has been handled
+     * or the driver is finished. Returns the number of handled events.
+     **/
+    pub fn run_until<P>(&mut self, mut predicate: P) -> u64
+    where
+        P: FnMut(&ExplorationEvent<Conf>) -> bool,
+    {
+        let mut handled = 0;
+        while let Some(event) = self.step() {
+            handled += 1;
+            if predicate(&event) {
+                break;
+            }
+        }
+        handled
+    }
+
+    /**
+     * Handles at most k events. Returns the number of handled events.
+     **/
+    pub fn run_n(&mut self, k: u64) -> u64 {
+        let mut handled = 0;
+        while handled < k && self.step().is_some() {
+            handled += 1;
+        }
+        handled
+    }
+
+    /**
+     * Adds a logger, which receives log_initialize_process right away
+     * and then the events that are yet to be handled.
+     * Returns its index.
+     **/
+    pub fn add_logger(&mut self, logger: Box<dyn AbstractProcessLogger<Conf> + 'a>) -> usize {
+        self.dispatcher.add_logger(logger, self.manager)
+    }
+
+    /**
+     * Removes a logger and gives it back.
+     * It does not receive log_terminate_process.
+     **/
+    pub fn remove_logger(&mut self, logger_index: usize) -> Option<Box<dyn AbstractProcessLogger<Conf> + 'a>> {
+        self.dispatcher.remove_logger(logger_index)
+    }
+
+    /**
+     * Handles the remaining events, calls log_terminate_process on every logger
+     * and returns the report of the whole run.
+     **/
+    pub fn finish(mut self) -> LoggersRunReport {
+        while self.step().is_some() {}
+        self.dispatcher.terminate(self.manager)
+    }
+}
@@ -0,0 +1,72 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::fmt;
+
+
+/**
+ * Error returned by a logger callback.
+ * It is collected by drive_loggers into a LoggersRunReport
+ * instead of being silently discarded.
+ **/
+#[derive(Debug)]
+pub enum LoggerError {
+    /** An I/O operation on the files produced by the logger failed. **/
+    Io {
+        context: String,
+        source: std::io::Error,
+    },
+    /** An external renderer (e.g. the dot binary) did not produce the expected output. **/
+    Render {
+        context: String,
+        detail: String,
+    },
+    /** Any other failure, e.g. raised by a user-defined logger. **/
+    Custom(String),
+}
+
+impl LoggerError {
+    pub fn io(context: impl Into<String>, source: std::io::Error) -> Self {
+        LoggerError::Io { context: context.into(), source }
+    }
+
+    pub fn render(context: impl Into<String>, detail: impl Into<String>) -> Self {
+        LoggerError::Render { context: context.into(), detail: detail.into() }
+    }
+}
+
+impl fmt::Display for LoggerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoggerError::Io { context, source } => {
+                write!(f, "I/O error while {} : {}", context, source)
+            }
+            LoggerError::Render { context, detail } => {
+                write!(f, "rendering error while {} : {}", context, detail)
+            }
+            LoggerError::Custom(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for LoggerError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LoggerError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
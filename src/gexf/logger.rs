@@ -0,0 +1,80 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::path::PathBuf;
+
+use graph_process_manager_core::process::config::AbstractProcessConfiguration;
+
+use crate::gexf::writer::to_gexf;
+use crate::graphml::describer::GraphMLProcessDescriber;
+use crate::graphml::recorder::ProcessGraphRecorder;
+use crate::logger_registry::LoggerWithProduct;
+
+
+/**
+ * Logger that writes the explored graph as a dynamic GEXF file "{output_file_name}.gexf"
+ * when the process terminates, so that Gephi's timeline can replay the exploration.
+ *
+ * The start of each element is the discovery index (see EventMeta) of the node
+ * that was discovered last when the element was logged:
+ * its own for a node, that of the newly discovered target for a step,
+ * and the current one for a step towards an already known node or a filtration result.
+ *
+ * Elements and their attributes are the same as with the GenericGraphMLLogger.
+ **/
+pub struct GenericGexfLogger<Conf : AbstractProcessConfiguration> {
+    pub describer : Box<dyn GraphMLProcessDescriber<Conf>>,
+    // ***
+    pub(crate) parent_folder : String,
+    pub(crate) output_file_name : String,
+    // ***
+    pub(crate) recorder : ProcessGraphRecorder
+}
+
+impl<Conf : AbstractProcessConfiguration> GenericGexfLogger<Conf> {
+
+    pub fn new(describer : Box<dyn GraphMLProcessDescriber<Conf>>,
+               parent_folder : String,
+               output_file_name : String) -> Self {
+        Self {
+            describer,
+            parent_folder,
+            output_file_name,
+            recorder : ProcessGraphRecorder::default()
+        }
+    }
+
+    pub fn get_output_path(&self) -> PathBuf {
+        [&self.parent_folder, &format!("{}.gexf", self.output_file_name)].iter().collect()
+    }
+
+    /** The GEXF document for the events logged so far. **/
+    pub fn to_gexf(&self) -> String {
+        to_gexf(&self.recorder.graph)
+    }
+}
+
+
+/**
+ * The product is the GEXF document.
+ **/
+impl<Conf : AbstractProcessConfiguration> LoggerWithProduct for GenericGexfLogger<Conf> {
+    type Product = String;
+
+    fn into_product(self) -> String {
+        to_gexf(&self.recorder.graph)
+    }
+}
@@ -0,0 +1,81 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::fmt::Write;
+
+use crate::graphml::document::{AttributeDomain, AttributedElement, AttributedGraph};
+use crate::util::xml_escape;
+
+
+/**
+ * Writes an AttributedGraph as a dynamic GEXF 1.3 graph,
+ * in which each element exists from its start (an integer) onwards.
+ **/
+pub(crate) fn to_gexf(graph: &AttributedGraph) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<gexf xmlns=\"http://gexf.net/1.3\" version=\"1.3\">\n");
+    xml.push_str("  <meta>\n    <creator>graph_process_manager_loggers</creator>\n  </meta>\n");
+    xml.push_str("  <graph mode=\"dynamic\" defaultedgetype=\"directed\" timeformat=\"integer\">\n");
+    for domain in [AttributeDomain::Node, AttributeDomain::Edge] {
+        let _ = writeln!(xml, "    <attributes class=\"{}\">", domain.as_str());
+        for (index, key) in graph.keys.iter().enumerate().filter(|(_, key)| key.domain == domain) {
+            let _ = writeln!(
+                xml,
+                "      <attribute id=\"{}\" title=\"{}\" type=\"{}\"/>",
+                index, xml_escape(&key.name), key.attr_type
+            );
+        }
+        xml.push_str("    </attributes>\n");
+    }
+    xml.push_str("    <nodes>\n");
+    for node in &graph.nodes {
+        let identification = format!(
+            "id=\"{}\" label=\"{}\" start=\"{}\"",
+            xml_escape(&node.id), xml_escape(&node.id), node.start
+        );
+        write_gexf_element(&mut xml, "node", &identification, node);
+    }
+    xml.push_str("    </nodes>\n");
+    xml.push_str("    <edges>\n");
+    for (index, edge) in graph.edges.iter().enumerate() {
+        let identification = format!(
+            "id=\"e{}\" source=\"{}\" target=\"{}\" start=\"{}\"",
+            index, xml_escape(&edge.id), xml_escape(edge.target.as_deref().unwrap_or_default()), edge.start
+        );
+        write_gexf_element(&mut xml, "edge", &identification, edge);
+    }
+    xml.push_str("    </edges>\n");
+    xml.push_str("  </graph>\n");
+    xml.push_str("</gexf>\n");
+    xml
+}
+
+
+fn write_gexf_element(xml: &mut String, tag: &str, identification: &str, element: &AttributedElement) {
+    if element.data.is_empty() {
+        let _ = writeln!(xml, "      <{} {}/>", tag, identification);
+        return;
+    }
+    let _ = writeln!(xml, "      <{} {}>", tag, identification);
+    xml.push_str("        <attvalues>\n");
+    for (index, value) in &element.data {
+        let _ = writeln!(xml, "          <attvalue for=\"{}\" value=\"{}\"/>", index, xml_escape(&value.to_string()));
+    }
+    xml.push_str("        </attvalues>\n");
+    let _ = writeln!(xml, "      </{}>", tag);
+}
+
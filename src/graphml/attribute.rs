@@ -0,0 +1,90 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::fmt;
+
+
+/**
+ * Value of a user-defined GraphML attribute.
+ * Its variant determines the attr.type of the corresponding key.
+ **/
+#[derive(Clone, PartialEq, Debug)]
+pub enum GraphMLAttributeValue {
+    Boolean(bool),
+    Long(i64),
+    Double(f64),
+    String(String),
+}
+
+impl GraphMLAttributeValue {
+    pub(crate) fn attr_type(&self) -> &'static str {
+        match self {
+            GraphMLAttributeValue::Boolean(_) => "boolean",
+            GraphMLAttributeValue::Long(_)    => "long",
+            GraphMLAttributeValue::Double(_)  => "double",
+            GraphMLAttributeValue::String(_)  => "string",
+        }
+    }
+}
+
+/**
+ * Writes the value in the lexical form of its XML Schema type,
+ * which both GraphML and GEXF readers expect.
+ **/
+impl fmt::Display for GraphMLAttributeValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphMLAttributeValue::Boolean(value) => write!(f, "{}", value),
+            GraphMLAttributeValue::Long(value)    => write!(f, "{}", value),
+            GraphMLAttributeValue::Double(value) if value.is_nan() => write!(f, "NaN"),
+            GraphMLAttributeValue::Double(value) if value.is_infinite() => {
+                write!(f, "{}", if *value > 0.0 { "INF" } else { "-INF" })
+            }
+            GraphMLAttributeValue::Double(value)  => write!(f, "{}", value),
+            GraphMLAttributeValue::String(value)  => write!(f, "{}", value),
+        }
+    }
+}
+
+impl From<bool> for GraphMLAttributeValue {
+    fn from(value: bool) -> Self {
+        GraphMLAttributeValue::Boolean(value)
+    }
+}
+
+impl From<i64> for GraphMLAttributeValue {
+    fn from(value: i64) -> Self {
+        GraphMLAttributeValue::Long(value)
+    }
+}
+
+impl From<f64> for GraphMLAttributeValue {
+    fn from(value: f64) -> Self {
+        GraphMLAttributeValue::Double(value)
+    }
+}
+
+impl From<String> for GraphMLAttributeValue {
+    fn from(value: String) -> Self {
+        GraphMLAttributeValue::String(value)
+    }
+}
+
+impl From<&str> for GraphMLAttributeValue {
+    fn from(value: &str) -> Self {
+        GraphMLAttributeValue::String(value.to_string())
+    }
+}
@@ -0,0 +1,51 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use graph_process_manager_core::process::config::AbstractProcessConfiguration;
+
+use crate::graphml::attribute::GraphMLAttributeValue;
+
+
+/**
+ * Describes the nodes, steps and filtration results of a process
+ * as the key/value pairs stored as GraphML data.
+ *
+ * The "kind" key is reserved by the logger to tell process nodes from filtration results
+ * (and steps from filtrations on edges): the loggers fail with a LoggerError::Custom on an attribute with that key.
+ * If the values given for a key do not all have the same type, the key is declared as a string.
+ **/
+pub trait GraphMLProcessDescriber<Conf: AbstractProcessConfiguration> {
+
+    fn describe_node(
+        &self,
+        ctx: &Conf::ContextAndParameterization,
+        node: &Conf::DomainSpecificNode,
+    ) -> Vec<(String, GraphMLAttributeValue)>;
+
+    fn describe_step(
+        &self,
+        ctx: &Conf::ContextAndParameterization,
+        step: &Conf::DomainSpecificStep,
+    ) -> Vec<(String, GraphMLAttributeValue)>;
+
+    /// Attributes of the node standing for a filtration result,
+    /// which is linked to the filtered node by an edge of kind "filtration".
+    fn describe_filtration_result(
+        &self,
+        ctx: &Conf::ContextAndParameterization,
+        filtration_result: &Conf::FiltrationResult,
+    ) -> Vec<(String, GraphMLAttributeValue)>;
+}
@@ -0,0 +1,154 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::graphml::attribute::GraphMLAttributeValue;
+use crate::util::xml_escape;
+
+
+#[derive(Clone, Copy, PartialEq, Debug, Eq, Hash)]
+pub(crate) enum AttributeDomain {
+    Node,
+    Edge,
+}
+
+impl AttributeDomain {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AttributeDomain::Node => "node",
+            AttributeDomain::Edge => "edge",
+        }
+    }
+}
+
+
+pub(crate) struct AttributeKey {
+    pub domain: AttributeDomain,
+    pub name: String,
+    pub attr_type: &'static str,
+}
+
+pub(crate) struct AttributedElement {
+    /** The id of a node, or the source of an edge. **/
+    pub id: String,
+    /** The target of an edge. **/
+    pub target: Option<String>,
+    /** When the element appears, for the formats that support dynamic graphs (i.e. GEXF). **/
+    #[cfg_attr(not(feature = "gexf"), allow(dead_code))]
+    pub start: u32,
+    /** Index of the key → value. **/
+    pub data: Vec<(usize, GraphMLAttributeValue)>,
+}
+
+
+/**
+ * A directed graph whose nodes and edges carry user-defined attributes,
+ * built element by element and written at once as GraphML or GEXF.
+ * Keys are declared as they are first used.
+ **/
+#[derive(Default)]
+pub(crate) struct AttributedGraph {
+    pub keys: Vec<AttributeKey>,
+    key_indices: HashMap<(AttributeDomain, String), usize>,
+    pub nodes: Vec<AttributedElement>,
+    pub edges: Vec<AttributedElement>,
+}
+
+impl AttributedGraph {
+
+    pub fn add_node(&mut self, id: String, start: u32, attributes: Vec<(String, GraphMLAttributeValue)>) {
+        let data = self.declare_keys(AttributeDomain::Node, attributes);
+        self.nodes.push(AttributedElement { id, target: None, start, data });
+    }
+
+    pub fn add_edge(
+        &mut self,
+        source: String,
+        target: String,
+        start: u32,
+        attributes: Vec<(String, GraphMLAttributeValue)>,
+    ) {
+        let data = self.declare_keys(AttributeDomain::Edge, attributes);
+        self.edges.push(AttributedElement { id: source, target: Some(target), start, data });
+    }
+
+    fn declare_keys(
+        &mut self,
+        domain: AttributeDomain,
+        attributes: Vec<(String, GraphMLAttributeValue)>,
+    ) -> Vec<(usize, GraphMLAttributeValue)> {
+        attributes
+            .into_iter()
+            .map(|(name, value)| {
+                let index = *self.key_indices.entry((domain, name.clone())).or_insert_with(|| {
+                    self.keys.push(AttributeKey { domain, name, attr_type: value.attr_type() });
+                    self.keys.len() - 1
+                });
+                let key = &mut self.keys[index];
+                if key.attr_type != value.attr_type() {
+                    key.attr_type = "string";
+                }
+                (index, value)
+            })
+            .collect()
+    }
+
+    pub fn to_graphml(&self) -> String {
+        let mut xml = String::new();
+        xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\" ");
+        xml.push_str("xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" ");
+        xml.push_str("xsi:schemaLocation=\"http://graphml.graphdrawing.org/xmlns ");
+        xml.push_str("http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd\">\n");
+        for (index, key) in self.keys.iter().enumerate() {
+            let _ = writeln!(
+                xml,
+                "  <key id=\"d{}\" for=\"{}\" attr.name=\"{}\" attr.type=\"{}\"/>",
+                index, key.domain.as_str(), xml_escape(&key.name), key.attr_type
+            );
+        }
+        xml.push_str("  <graph id=\"G\" edgedefault=\"directed\">\n");
+        for node in &self.nodes {
+            let identification = format!("id=\"{}\"", xml_escape(&node.id));
+            write_graphml_element(&mut xml, "node", &identification, &node.data);
+        }
+        for (index, edge) in self.edges.iter().enumerate() {
+            let identification = format!(
+                "id=\"e{}\" source=\"{}\" target=\"{}\"",
+                index, xml_escape(&edge.id), xml_escape(edge.target.as_deref().unwrap_or_default())
+            );
+            write_graphml_element(&mut xml, "edge", &identification, &edge.data);
+        }
+        xml.push_str("  </graph>\n");
+        xml.push_str("</graphml>\n");
+        xml
+    }
+}
+
+
+fn write_graphml_element(xml: &mut String, tag: &str, identification: &str, data: &[(usize, GraphMLAttributeValue)]) {
+    if data.is_empty() {
+        let _ = writeln!(xml, "    <{} {}/>", tag, identification);
+        return;
+    }
+    let _ = writeln!(xml, "    <{} {}>", tag, identification);
+    for (index, value) in data {
+        let _ = writeln!(xml, "      <data key=\"d{}\">{}</data>", index, xml_escape(&value.to_string()));
+    }
+    let _ = writeln!(xml, "    </{}>", tag);
+}
@@ -0,0 +1,79 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::fs;
+
+use graph_process_manager_core::process::config::AbstractProcessConfiguration;
+use graph_process_manager_core::process::manager::GenericProcessManager;
+
+use crate::control::{LoggerControl, TerminationReason};
+use crate::error::LoggerError;
+use crate::graphml::logger::GenericGraphMLLogger;
+use crate::logger::{AbstractProcessLogger, LoggerResult};
+use crate::meta::EventMeta;
+use crate::util::create_folder;
+
+
+impl<Conf: AbstractProcessConfiguration + 'static> AbstractProcessLogger<Conf>
+    for GenericGraphMLLogger<Conf>
+{
+    fn log_new_node(
+        &mut self,
+        ctx: &Conf::ContextAndParameterization,
+        meta: &EventMeta<Conf>,
+        new_node_id: u32,
+        new_node: &Conf::DomainSpecificNode,
+    ) -> LoggerResult {
+        self.recorder.on_new_node(self.describer.as_ref(), ctx, meta, new_node_id, new_node)?;
+        Ok(LoggerControl::Continue)
+    }
+
+    fn log_new_step(
+        &mut self,
+        ctx: &Conf::ContextAndParameterization,
+        _meta: &EventMeta<Conf>,
+        origin_node_id: u32,
+        step: &Conf::DomainSpecificStep,
+        target_node_id: u32,
+        _target_node: &Conf::DomainSpecificNode,
+    ) -> LoggerResult {
+        self.recorder.on_new_step(self.describer.as_ref(), ctx, origin_node_id, step, target_node_id)?;
+        Ok(LoggerControl::Continue)
+    }
+
+    fn log_filtered(
+        &mut self,
+        ctx: &Conf::ContextAndParameterization,
+        _meta: &EventMeta<Conf>,
+        parent_node_id: u32,
+        filtration_result: &Conf::FiltrationResult,
+    ) -> LoggerResult {
+        self.recorder.on_filtered(self.describer.as_ref(), ctx, parent_node_id, filtration_result)?;
+        Ok(LoggerControl::Continue)
+    }
+
+    fn log_terminate_process(
+        &mut self,
+        _manager: &GenericProcessManager<Conf>,
+        _reason: &TerminationReason,
+    ) -> LoggerResult {
+        create_folder(&self.parent_folder)?;
+        let path = self.get_output_path();
+        fs::write(&path, self.recorder.graph.to_graphml())
+            .map_err(|e| LoggerError::io(format!("writing '{}'", path.display()), e))?;
+        Ok(LoggerControl::Continue)
+    }
+}
@@ -0,0 +1,75 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::path::PathBuf;
+
+use graph_process_manager_core::process::config::AbstractProcessConfiguration;
+
+use crate::graphml::describer::GraphMLProcessDescriber;
+use crate::graphml::recorder::ProcessGraphRecorder;
+use crate::logger_registry::LoggerWithProduct;
+
+
+/**
+ * Logger that writes the explored graph as a GraphML file "{output_file_name}.graphml"
+ * (readable by yEd, NetworkX, Gephi...) when the process terminates.
+ *
+ * Process nodes have ids "n{node_id}" and filtration results "f{counter}".
+ * Node and edge data are those given by the describer, plus the reserved "kind" key.
+ **/
+pub struct GenericGraphMLLogger<Conf : AbstractProcessConfiguration> {
+    pub describer : Box<dyn GraphMLProcessDescriber<Conf>>,
+    // ***
+    pub(crate) parent_folder : String,
+    pub(crate) output_file_name : String,
+    // ***
+    pub(crate) recorder : ProcessGraphRecorder
+}
+
+impl<Conf : AbstractProcessConfiguration> GenericGraphMLLogger<Conf> {
+
+    pub fn new(describer : Box<dyn GraphMLProcessDescriber<Conf>>,
+               parent_folder : String,
+               output_file_name : String) -> Self {
+        Self {
+            describer,
+            parent_folder,
+            output_file_name,
+            recorder : ProcessGraphRecorder::default()
+        }
+    }
+
+    pub fn get_output_path(&self) -> PathBuf {
+        [&self.parent_folder, &format!("{}.graphml", self.output_file_name)].iter().collect()
+    }
+
+    /** The GraphML document for the events logged so far. **/
+    pub fn to_graphml(&self) -> String {
+        self.recorder.graph.to_graphml()
+    }
+}
+
+
+/**
+ * The product is the GraphML document.
+ **/
+impl<Conf : AbstractProcessConfiguration> LoggerWithProduct for GenericGraphMLLogger<Conf> {
+    type Product = String;
+
+    fn into_product(self) -> String {
+        self.recorder.graph.to_graphml()
+    }
+}
@@ -0,0 +1,111 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use graph_process_manager_core::process::config::AbstractProcessConfiguration;
+
+use crate::graphml::attribute::GraphMLAttributeValue;
+use crate::graphml::describer::GraphMLProcessDescriber;
+use crate::error::LoggerError;
+use crate::graphml::document::AttributedGraph;
+use crate::meta::EventMeta;
+
+
+/**
+ * Builds the AttributedGraph of a process from its events, for the GraphML and GEXF loggers.
+ *
+ * Every element starts at the discovery index of the node that was discovered last when it was logged,
+ * i.e. its own for a node, that of its target for a step towards a new node.
+ **/
+#[derive(Default)]
+pub(crate) struct ProcessGraphRecorder {
+    pub graph: AttributedGraph,
+    filtration_counter: u32,
+    last_discovery_index: u32,
+}
+
+const KIND_KEY: &str = "kind";
+
+/** Adds the reserved "kind" key to the attributes given by the describer, which must not use it. **/
+fn with_kind(
+    kind: &str,
+    mut attributes: Vec<(String, GraphMLAttributeValue)>,
+) -> Result<Vec<(String, GraphMLAttributeValue)>, LoggerError> {
+    if attributes.iter().any(|(key, _)| key == KIND_KEY) {
+        return Err(LoggerError::Custom(format!(
+            "describing a {} : the \"{}\" key is reserved by the logger",
+            kind, KIND_KEY
+        )));
+    }
+    attributes.insert(0, (KIND_KEY.to_string(), kind.into()));
+    Ok(attributes)
+}
+
+impl ProcessGraphRecorder {
+
+    pub fn on_new_node<Conf: AbstractProcessConfiguration>(
+        &mut self,
+        describer: &dyn GraphMLProcessDescriber<Conf>,
+        ctx: &Conf::ContextAndParameterization,
+        meta: &EventMeta<Conf>,
+        new_node_id: u32,
+        new_node: &Conf::DomainSpecificNode,
+    ) -> Result<(), LoggerError> {
+        if let Some(discovery_index) = meta.discovery_index {
+            self.last_discovery_index = discovery_index;
+        }
+        let attributes = with_kind("node", describer.describe_node(ctx, new_node))?;
+        self.graph.add_node(format!("n{}", new_node_id), self.last_discovery_index, attributes);
+        Ok(())
+    }
+
+    pub fn on_new_step<Conf: AbstractProcessConfiguration>(
+        &mut self,
+        describer: &dyn GraphMLProcessDescriber<Conf>,
+        ctx: &Conf::ContextAndParameterization,
+        origin_node_id: u32,
+        step: &Conf::DomainSpecificStep,
+        target_node_id: u32,
+    ) -> Result<(), LoggerError> {
+        let attributes = with_kind("step", describer.describe_step(ctx, step))?;
+        self.graph.add_edge(
+            format!("n{}", origin_node_id),
+            format!("n{}", target_node_id),
+            self.last_discovery_index,
+            attributes,
+        );
+        Ok(())
+    }
+
+    pub fn on_filtered<Conf: AbstractProcessConfiguration>(
+        &mut self,
+        describer: &dyn GraphMLProcessDescriber<Conf>,
+        ctx: &Conf::ContextAndParameterization,
+        parent_node_id: u32,
+        filtration_result: &Conf::FiltrationResult,
+    ) -> Result<(), LoggerError> {
+        self.filtration_counter += 1;
+        let filtration_id = format!("f{}", self.filtration_counter);
+        let attributes = with_kind("filtration", describer.describe_filtration_result(ctx, filtration_result))?;
+        self.graph.add_node(filtration_id.clone(), self.last_discovery_index, attributes);
+        self.graph.add_edge(
+            format!("n{}", parent_node_id),
+            filtration_id,
+            self.last_discovery_index,
+            with_kind("filtration", vec![])?,
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::{HashMap, HashSet};
+
+
+/**
+ * A chain of nodes that each have exactly one parent and one child,
+ * from the kept node start to the kept node end.
+ **/
+#[derive(Clone, PartialEq, Debug, Eq)]
+pub(crate) struct LinearChain {
+    pub start: u32,
+    pub interior: Vec<u32>,
+    pub end: u32,
+}
+
+impl LinearChain {
+    /** Number of steps replaced by the collapsed chain. **/
+    pub fn elided_steps(&self) -> usize {
+        self.interior.len() + 1
+    }
+}
+
+
+/**
+ * Finds the maximal linear chains of the graph given by its steps (origin, target).
+ * A node is inside a chain if it has exactly one incoming and one outgoing step,
+ * is not filtered, and is in the same phase as its parent and its child.
+ * The initial node and the targets of back-edges are never inside a chain, so that a cycle is not collapsed
+ * into a chain that starts and ends at one of its own interior nodes.
+ **/
+pub(crate) fn find_linear_chains(
+    steps: &[(u32, u32)],
+    filtered_nodes: &HashSet<u32>,
+    phases: &HashMap<u32, usize>,
+    initial_node: Option<u32>,
+) -> Vec<LinearChain> {
+    let mut parents: HashMap<u32, Vec<u32>> = HashMap::new();
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    for (origin, target) in steps {
+        children.entry(*origin).or_default().push(*target);
+        parents.entry(*target).or_default().push(*origin);
+    }
+    let single = |map: &HashMap<u32, Vec<u32>>, node: u32| match map.get(&node).map(Vec::as_slice) {
+        Some([other]) => Some(*other),
+        _ => None,
+    };
+    let kept_nodes: HashSet<u32> = match initial_node {
+        Some(initial_node) => {
+            let mut kept_nodes = back_edge_targets(&children, initial_node);
+            kept_nodes.insert(initial_node);
+            kept_nodes
+        }
+        None => HashSet::new(),
+    };
+    let is_interior = |node: u32| -> bool {
+        match (single(&parents, node), single(&children, node)) {
+            (Some(parent), Some(child)) => {
+                !filtered_nodes.contains(&node)
+                    && !kept_nodes.contains(&node)
+                    && phases.get(&node) == phases.get(&parent)
+                    && phases.get(&node) == phases.get(&child)
+            }
+            _ => false,
+        }
+    };
+
+    let mut chains = vec![];
+    // chains start from the kept parent of their first interior node, in the order of the steps
+    for (origin, target) in steps {
+        if is_interior(*origin) || !is_interior(*target) {
+            continue;
+        }
+        let mut interior = vec![*target];
+        let mut end = single(&children, *target).unwrap();
+        while is_interior(end) {
+            interior.push(end);
+            end = single(&children, end).unwrap();
+        }
+        chains.push(LinearChain { start: *origin, interior, end });
+    }
+    chains
+}
+
+
+/** Targets of the steps that go back to a node on the current path of a depth-first search from the initial node. **/
+fn back_edge_targets(children: &HashMap<u32, Vec<u32>>, initial_node: u32) -> HashSet<u32> {
+    let mut targets = HashSet::new();
+    let mut visited: HashSet<u32> = HashSet::from([initial_node]);
+    let mut on_path: HashSet<u32> = HashSet::from([initial_node]);
+    // (node, index of its next child to visit), iteratively, as the graph may be deep
+    let mut path: Vec<(u32, usize)> = vec![(initial_node, 0)];
+    while let Some((node, next_child)) = path.last_mut() {
+        let node = *node;
+        let Some(child) = children.get(&node).and_then(|node_children| node_children.get(*next_child)).copied() else {
+            on_path.remove(&node);
+            path.pop();
+            continue;
+        };
+        *next_child += 1;
+        if on_path.contains(&child) {
+            targets.insert(child);
+        } else if visited.insert(child) {
+            on_path.insert(child);
+            path.push((child, 0));
+        }
+    }
+    targets
+}
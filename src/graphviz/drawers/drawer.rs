@@ -18,7 +18,10 @@ use std::path::Path;
 
 use graph_process_manager_core::process::config::AbstractProcessConfiguration;
 use graphviz_dot_builder::colors::GraphvizColor;
+use graphviz_dot_builder::edge::style::{GraphvizEdgeStyleItem, GvEdgeLineStyle};
+use graphviz_dot_builder::item::node::style::GraphvizNodeStyleItem;
 
+use crate::graphviz::format::{GraphVizNodeExplorationStatus, GraphVizStepEdgeKind};
 use crate::graphviz::item::BuiltinGraphvizLoggerItemStyle;
 
 
@@ -48,12 +51,47 @@ pub trait GraphVizProcessDrawer<Conf: AbstractProcessConfiguration> {
         image_file_path: &Path,
     ) -> BuiltinGraphvizLoggerItemStyle;
 
+    /// Text of the label of a step drawn as an edge (`GraphVizLoggerStepFormat::EdgeLabel`),
+    /// in which case `draw_step` is not called.
+    /// Returns `None` (the default) to use the text of the `Default` style given by `draw_step`;
+    /// a step drawn as a `CustomImage` is then labelled with the ids of its origin and target,
+    /// and its image is not used.
+    fn step_label_text(
+        &self,
+        _ctx: &Conf::ContextAndParameterization,
+        _step: &Conf::DomainSpecificStep,
+    ) -> Option<String> { None }
+
     fn step_edge_color(
         &self,
         ctx: &Conf::ContextAndParameterization,
         step: &Conf::DomainSpecificStep,
     ) -> GraphvizColor;
 
+    /// Attributes of the edges of a step according to its class, which replace those of the same kind
+    /// (e.g. the color given by `step_edge_color`).
+    /// By default, back-edges are dashed, cross-edges are dotted, forward-edges are bold,
+    /// and back-edges and cross-edges do not constrain the ranks of the nodes.
+    fn step_edge_kind_style(
+        &self,
+        _ctx: &Conf::ContextAndParameterization,
+        _step: &Conf::DomainSpecificStep,
+        kind: GraphVizStepEdgeKind,
+    ) -> Vec<GraphvizEdgeStyleItem> {
+        match kind {
+            GraphVizStepEdgeKind::Tree => vec![],
+            GraphVizStepEdgeKind::Back => vec![
+                GraphvizEdgeStyleItem::LineStyle(GvEdgeLineStyle::Dashed),
+                GraphvizEdgeStyleItem::Constraint(false),
+            ],
+            GraphVizStepEdgeKind::Forward => vec![GraphvizEdgeStyleItem::LineStyle(GvEdgeLineStyle::Bold)],
+            GraphVizStepEdgeKind::Cross => vec![
+                GraphvizEdgeStyleItem::LineStyle(GvEdgeLineStyle::Dotted),
+                GraphvizEdgeStyleItem::Constraint(false),
+            ],
+        }
+    }
+
     fn draw_filter(
         &self,
         ctx: &Conf::ContextAndParameterization,
@@ -77,4 +115,32 @@ pub trait GraphVizProcessDrawer<Conf: AbstractProcessConfiguration> {
 
     /// Background fill color for the cluster that groups all nodes of `phase_id`.
     fn phase_color(&self, _phase_id: usize) -> GraphvizColor { GraphvizColor::white }
+
+    /// Attributes added at termination to the drawn nodes with a given exploration status,
+    /// on top of those given by `draw_node_view` (they apply to the whole cluster of an anchored node).
+    /// Returns `None` (the default) to leave the nodes of every status undecorated.
+    fn node_status_decoration(
+        &self,
+        _status: GraphVizNodeExplorationStatus,
+    ) -> Option<Vec<GraphvizNodeStyleItem>> { None }
+
+    /// Score of a node, for heatmaps of the `GraphVizNodeMetric::Score` metric.
+    /// Returns `None` (the default) to leave the node uncoloured.
+    fn node_score(
+        &self,
+        _ctx: &Conf::ContextAndParameterization,
+        _node: &Conf::DomainSpecificNode,
+    ) -> Option<f64> { None }
+
+    /// Whether a node is of interest (e.g. a verdict was reached there), in which case
+    /// the path from the initial node to it is highlighted at termination.
+    /// Returns `false` (the default) for every node.
+    fn is_witness_target(
+        &self,
+        _ctx: &Conf::ContextAndParameterization,
+        _node: &Conf::DomainSpecificNode,
+    ) -> bool { false }
+
+    /// Color of the nodes and steps on the highlighted witness paths.
+    fn witness_path_color(&self) -> GraphvizColor { GraphvizColor::red }
 }
@@ -0,0 +1,72 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::HashMap;
+
+use crate::graphviz::format::GraphVizStepEdgeKind;
+
+
+/**
+ * Classifies the steps according to the tree of the steps through which the nodes were discovered.
+ * A new node is immediately followed by the step that discovered it, which is its tree edge.
+ **/
+#[derive(Default)]
+pub(crate) struct StepClassifier {
+    // node → origin of its tree edge
+    tree_parents: HashMap<u32, u32>,
+    // the last new node, while the step that discovered it has not been classified
+    undiscovered_by_step: Option<u32>,
+}
+
+impl StepClassifier {
+
+    pub fn on_new_node(&mut self, node_id: u32) {
+        self.undiscovered_by_step = Some(node_id);
+    }
+
+    pub fn classify(&mut self, origin_node_id: u32, target_node_id: u32) -> GraphVizStepEdgeKind {
+        if self.undiscovered_by_step == Some(target_node_id) {
+            self.undiscovered_by_step = None;
+            self.tree_parents.insert(target_node_id, origin_node_id);
+            GraphVizStepEdgeKind::Tree
+        } else if self.is_tree_ancestor(target_node_id, origin_node_id) {
+            GraphVizStepEdgeKind::Back
+        } else if self.is_tree_ancestor(origin_node_id, target_node_id) {
+            GraphVizStepEdgeKind::Forward
+        } else {
+            GraphVizStepEdgeKind::Cross
+        }
+    }
+
+    /** The origin of the tree edge of each node that has one. **/
+    pub fn tree_parents(&self) -> &HashMap<u32, u32> {
+        &self.tree_parents
+    }
+
+    /** Whether ancestor is node or one of its ancestors in the tree. **/
+    fn is_tree_ancestor(&self, ancestor: u32, node: u32) -> bool {
+        let mut current = node;
+        loop {
+            if current == ancestor {
+                return true;
+            }
+            match self.tree_parents.get(&current) {
+                Some(parent) => current = *parent,
+                None => return false,
+            }
+        }
+    }
+}
@@ -16,14 +16,54 @@ limitations under the License.
 
 
 
-#[derive(Clone, PartialEq, Debug, Eq, Hash)]
-pub enum GraphVizProcessLoggerLayout {
-    Horizontal,
-    Vertical
-}
+pub use crate::layout::ProcessLoggerLayout as GraphVizProcessLoggerLayout;
 
 #[derive(Clone, Copy, PartialEq, Debug, Eq, Hash)]
 pub enum GraphVizLoggerNodeFormat {
     AnchoredCluster,
     SimpleNode
-}
\ No newline at end of file
+}
+
+/**
+ * How the steps of the process are drawn.
+ **/
+#[derive(Clone, Copy, PartialEq, Debug, Eq, Hash, Default)]
+pub enum GraphVizLoggerStepFormat {
+    /** A node drawn by draw_step, between two edges from the origin and to the target. **/
+    #[default]
+    StepNode,
+    /**
+     * A single edge from the origin to the target, labelled with the text given by step_label_text,
+     * or else with the text of the style given by draw_step.
+     **/
+    EdgeLabel
+}
+
+/**
+ * How far a drawn node has been explored when the process terminates.
+ **/
+#[derive(Clone, Copy, PartialEq, Debug, Eq, Hash)]
+pub enum GraphVizNodeExplorationStatus {
+    /** The node has no children (NodeWithoutChildren). **/
+    Leaf,
+    /** All the children of the node have been processed (AllChildrenProcessed). **/
+    Expanded,
+    /** The node has been discovered but not expanded, e.g. because the run was cut short. **/
+    Frontier
+}
+
+/**
+ * Class of a step with respect to the tree of the steps through which the nodes were discovered.
+ * Steps other than tree edges only occur in memoized processes.
+ **/
+#[derive(Clone, Copy, PartialEq, Debug, Eq, Hash)]
+pub enum GraphVizStepEdgeKind {
+    /** The step through which its target was discovered. **/
+    Tree,
+    /** A step towards an ancestor of its origin (or towards its origin itself). **/
+    Back,
+    /** A step towards a descendant of its origin, other than through a tree edge. **/
+    Forward,
+    /** A step towards a node that is neither an ancestor nor a descendant of its origin. **/
+    Cross
+}
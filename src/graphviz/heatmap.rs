@@ -0,0 +1,157 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::HashMap;
+
+use graphviz_dot_builder::colors::GraphvizColor;
+
+
+/**
+ * A numeric metric of the drawn nodes, computed at termination over the recorded graph
+ * (the nodes left out by GraphVizOutputLimits do not count).
+ * The subtrees are those of the tree of the steps through which the nodes were discovered.
+ **/
+#[derive(Clone, Copy, PartialEq, Debug, Eq, Hash)]
+pub enum GraphVizNodeMetric {
+    /** Number of nodes in the subtree of the node, including itself. **/
+    SubtreeSize,
+    /** Number of filtrations of the node and of the nodes of its subtree. **/
+    FilteredDescendants,
+    /** Number of leaves (nodes without children) in the subtree of the node. **/
+    LeavesReached,
+    /** The score given by GraphVizProcessDrawer::node_score (nodes without score are not coloured). **/
+    Score,
+}
+
+impl GraphVizNodeMetric {
+    pub(crate) fn describe(&self) -> &'static str {
+        match self {
+            GraphVizNodeMetric::SubtreeSize => "subtree size",
+            GraphVizNodeMetric::FilteredDescendants => "filtered descendants",
+            GraphVizNodeMetric::LeavesReached => "leaves reached",
+            GraphVizNodeMetric::Score => "score",
+        }
+    }
+}
+
+
+/**
+ * Colouring of the fills of the drawn nodes according to a metric, at termination,
+ * with a key of the colour ramp next to the legend.
+ **/
+#[derive(Clone, PartialEq, Eq)]
+pub struct GraphVizHeatmapOptions {
+    pub metric: GraphVizNodeMetric,
+    /** Whether the values are mapped to the ramp on a logarithmic scale (false by default). **/
+    pub logarithmic: bool,
+    /** The colours from the lowest to the highest values (yellow to red by default). **/
+    pub ramp: Vec<GraphvizColor>,
+}
+
+impl GraphVizHeatmapOptions {
+    pub fn new(metric: GraphVizNodeMetric) -> Self {
+        Self {
+            metric,
+            logarithmic: false,
+            ramp: vec![
+                GraphvizColor::lightyellow,
+                GraphvizColor::lemonchiffon,
+                GraphvizColor::khaki1,
+                GraphvizColor::gold,
+                GraphvizColor::orange,
+                GraphvizColor::darkorange,
+                GraphvizColor::orangered,
+                GraphvizColor::red,
+                GraphvizColor::red3,
+            ],
+        }
+    }
+}
+
+
+/**
+ * Adds to the value of each node those of the nodes of its subtree,
+ * given the parent of each node in the discovery tree.
+ **/
+pub(crate) fn aggregate_over_subtrees(
+    own_values: &HashMap<u32, f64>,
+    tree_parents: &HashMap<u32, u32>,
+) -> HashMap<u32, f64> {
+    let mut values = own_values.clone();
+    // a node is discovered, and hence numbered, after its parent
+    let mut node_ids: Vec<u32> = own_values.keys().copied().collect();
+    node_ids.sort_unstable_by(|id1, id2| id2.cmp(id1));
+    for node_id in node_ids {
+        if let Some(parent) = tree_parents.get(&node_id) {
+            let value = values[&node_id];
+            if let Some(parent_value) = values.get_mut(parent) {
+                *parent_value += value;
+            }
+        }
+    }
+    values
+}
+
+
+/** Maps the values between min and max to the colours of a ramp of length ramp_len. **/
+pub(crate) struct HeatmapScale {
+    min: f64,
+    max: f64,
+    logarithmic: bool,
+    ramp_len: usize,
+}
+
+impl HeatmapScale {
+
+    pub fn new<'a>(values: impl Iterator<Item = &'a f64>, logarithmic: bool, ramp_len: usize) -> Option<Self> {
+        let (min, max) = values
+            .filter(|value| value.is_finite())
+            .fold(None, |bounds: Option<(f64, f64)>, value| match bounds {
+                None => Some((*value, *value)),
+                Some((min, max)) => Some((min.min(*value), max.max(*value))),
+            })?;
+        Some(Self { min, max, logarithmic, ramp_len: ramp_len.max(1) })
+    }
+
+    // position in [0, 1] of a value between min and max
+    fn position(&self, value: f64) -> f64 {
+        let (value, max) = if self.logarithmic {
+            ((value - self.min).ln_1p(), (self.max - self.min).ln_1p())
+        } else {
+            (value - self.min, self.max - self.min)
+        };
+        if max > 0.0 { (value / max).clamp(0.0, 1.0) } else { 0.0 }
+    }
+
+    pub fn ramp_index(&self, value: f64) -> usize {
+        ((self.position(value) * self.ramp_len as f64) as usize).min(self.ramp_len - 1)
+    }
+
+    /** The lowest value mapped to each colour of the ramp. **/
+    pub fn lower_bound(&self, ramp_index: usize) -> f64 {
+        let position = ramp_index as f64 / self.ramp_len as f64;
+        if self.logarithmic {
+            self.min + ((self.max - self.min).ln_1p() * position).exp_m1()
+        } else {
+            self.min + (self.max - self.min) * position
+        }
+    }
+
+    /** Number of colours of the key, a single one if all the values are equal. **/
+    pub fn key_len(&self) -> usize {
+        if self.max > self.min { self.ramp_len } else { 1 }
+    }
+}
@@ -14,34 +14,652 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use std::fs;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
 use std::path::PathBuf;
+use std::time::Duration;
 
 use graphviz_dot_builder::edge::edge::GraphVizEdge;
-use graphviz_dot_builder::edge::style::{GraphvizEdgeStyleItem, GvArrowHeadSide, GvArrowHeadStyle};
+use graphviz_dot_builder::graph::graph::GraphVizDiGraph;
+use graphviz_dot_builder::colors::GraphvizColor;
+use graphviz_dot_builder::edge::style::{GraphvizEdgeStyleItem, GvArrowHeadSide, GvArrowHeadStyle, GvEdgeLineStyle};
 use graphviz_dot_builder::item::node::node::GraphVizNode;
-use graphviz_dot_builder::item::node::style::{GraphvizNodeStyleItem, GvNodeStyleKind};
+use graphviz_dot_builder::item::node::style::{GraphvizNodeStyleItem, GvNodeShape, GvNodeStyleKind};
 use graphviz_dot_builder::item::item::GraphVizGraphItem;
 use graphviz_dot_builder::item::cluster::GraphVizCluster;
-use graphviz_dot_builder::traits::{DotBuildable, DotPrintable};
+use graphviz_dot_builder::traits::{DotBuildable, DotPrintable, DotTranslatable};
 
 use graph_process_manager_core::process::config::AbstractProcessConfiguration;
 use graph_process_manager_core::process::manager::GenericProcessManager;
 
-use crate::graphviz::format::GraphVizLoggerNodeFormat;
+use crate::control::{LoggerControl, TerminationReason};
+use crate::error::LoggerError;
+use crate::graphviz::chains::find_linear_chains;
+use crate::graphviz::format::{GraphVizLoggerNodeFormat, GraphVizLoggerStepFormat, GraphVizNodeExplorationStatus};
+use crate::graphviz::heatmap::{aggregate_over_subtrees, GraphVizNodeMetric, HeatmapScale};
+use crate::graphviz::item::BuiltinGraphvizLoggerItemStyle;
 use crate::graphviz::logger::GenericGraphVizLogger;
+use crate::graphviz::snapshot::html_player;
 use crate::graphviz::util::*;
-use crate::logger::AbstractProcessLogger;
+use crate::graphviz::witness::{find_witness_paths, WitnessPaths};
+use crate::logger::{AbstractProcessLogger, LoggerResult};
+use crate::meta::EventMeta;
+use crate::util::{check_dot_output, create_folder, reset_folder};
+
+
+impl<Conf: AbstractProcessConfiguration> GenericGraphVizLogger<Conf> {
+
+    fn start_dot_stream(&mut self) -> Result<(), LoggerError> {
+        let path = self.get_dot_file_path();
+        let file = File::create(&path)
+            .map_err(|e| LoggerError::io(format!("creating '{}'", path.display()), e))?;
+        self.dot_stream = Some(BufWriter::new(file));
+        // same header as GraphVizDiGraph::to_dot_string, but strict so that restating an edge restyles it
+        let mut header = "strict digraph G {\ncompound=true;".to_string();
+        for item in &self.graph.style {
+            header.push_str(&format!("\n{};", item.to_dot_string()));
+        }
+        self.write_to_dot_stream(&header)
+    }
+
+    // flushed at once, so that the file is complete up to the last event if the process crashes
+    fn write_to_dot_stream(&mut self, text: &str) -> Result<(), LoggerError> {
+        let path = self.get_dot_file_path();
+        if let Some(stream) = self.dot_stream.as_mut() {
+            stream
+                .write_all(text.as_bytes())
+                .and_then(|_| stream.flush())
+                .map_err(|e| LoggerError::io(format!("writing '{}'", path.display()), e))?;
+        }
+        Ok(())
+    }
+
+    /** Id of the DOT node to which the edges from or to a process node are attached, and its cluster if any. **/
+    fn edge_endpoint(&self, node_id: u32) -> (String, Option<String>) {
+        match self.node_format() {
+            GraphVizLoggerNodeFormat::AnchoredCluster => (get_anchor_id("", node_id), Some(get_node_id("", node_id))),
+            GraphVizLoggerNodeFormat::SimpleNode => (get_node_id("", node_id), None),
+        }
+    }
+
+    /**
+     * Replaces the linear chains of the graph built in memory with "n steps elided" edges.
+     * Returns the ids of the process nodes that have been removed.
+     **/
+    fn collapse_linear_chains(&mut self) -> HashSet<u32> {
+        let chains = find_linear_chains(
+            &self.process_steps,
+            &self.filtered_nodes,
+            &self.nodes_id_to_process_phase_id,
+            self.initial_node_id,
+        );
+        let mut collapsed: HashSet<u32> = HashSet::new();
+        // ids of the DOT nodes of the chains, to which all the edges of the chains are attached
+        let mut removed: HashSet<String> = HashSet::new();
+        for chain in &chains {
+            let mut previous = chain.start;
+            for node_id in chain.interior.iter().chain(std::iter::once(&chain.end)) {
+                removed.insert(get_step_id("", previous, *node_id));
+                previous = *node_id;
+            }
+            for node_id in &chain.interior {
+                collapsed.insert(*node_id);
+                removed.insert(get_node_id("", *node_id));
+                removed.insert(get_anchor_id("", *node_id));
+            }
+        }
+        let is_kept_item = |item: &GraphVizGraphItem| match item {
+            GraphVizGraphItem::Node(node) => !removed.contains(&node.id),
+            GraphVizGraphItem::Cluster(cluster) => !removed.contains(&cluster.id),
+        };
+        let is_kept_edge = |edge: &GraphVizEdge| {
+            !removed.contains(&edge.origin_node_id) && !removed.contains(&edge.target_node_id)
+        };
+        self.graph.items.retain(is_kept_item);
+        self.graph.edges.retain(is_kept_edge);
+        for cluster in self.process_phases_clusters.values_mut() {
+            cluster.items.retain(|item| is_kept_item(item));
+            cluster.edges.retain(is_kept_edge);
+        }
+
+        for chain in chains {
+            let (origin_id, origin_cluster) = self.edge_endpoint(chain.start);
+            let (target_id, target_cluster) = self.edge_endpoint(chain.end);
+            let elided = GraphVizEdge::new(
+                origin_id,
+                origin_cluster,
+                target_id,
+                target_cluster,
+                vec![
+                    GraphvizEdgeStyleItem::Head(GvArrowHeadStyle::Vee(GvArrowHeadSide::Both)),
+                    GraphvizEdgeStyleItem::Color(GraphvizColor::gray50),
+                    GraphvizEdgeStyleItem::LineStyle(GvEdgeLineStyle::Dashed),
+                    GraphvizEdgeStyleItem::Label(format!("{} steps elided", chain.elided_steps())),
+                ],
+            );
+            match (
+                self.nodes_id_to_process_phase_id.get(&chain.start),
+                self.nodes_id_to_process_phase_id.get(&chain.end),
+            ) {
+                (Some(orig_phase), Some(targ_phase)) if orig_phase == targ_phase => {
+                    self.process_phases_clusters.get_mut(orig_phase).unwrap().add_edge(elided);
+                }
+                _ => self.graph.add_edge(elided),
+            }
+        }
+        collapsed
+    }
+
+    /**
+     * Raw DOT statements for annotate_discovery and align_depths, which graphviz_dot_builder cannot express,
+     * and which restate nodes already declared, along with the phase in the cluster of which they are to be written.
+     **/
+    fn discovery_items(&self, collapsed: &HashSet<u32>) -> Vec<(Option<usize>, GraphVizGraphItem)> {
+        let mut items = vec![];
+        let nodes_discovery = self.nodes_discovery.iter().filter(|(node_id, _, _)| !collapsed.contains(node_id));
+        if self.configuration.annotate_discovery {
+            for (node_id, discovery_index, depth) in nodes_discovery.clone() {
+                let annotation = escape_dot_string(&format!(
+                    "#{}, depth {}",
+                    discovery_index.map_or("?".to_string(), |index| index.to_string()),
+                    depth.map_or("?".to_string(), |depth| depth.to_string()),
+                ));
+                // restating a node or cluster, in the same parent, adds the annotation to it
+                let item = match self.node_format() {
+                    GraphVizLoggerNodeFormat::SimpleNode => {
+                        raw_dot_statement(format!("{} [xlabel=\"{}\"]", get_node_id("", *node_id), annotation))
+                    }
+                    GraphVizLoggerNodeFormat::AnchoredCluster => GraphVizGraphItem::Cluster(GraphVizCluster::new(
+                        get_node_id("", *node_id),
+                        vec![GraphvizNodeStyleItem::Label(annotation)],
+                        vec![],
+                        vec![],
+                    )),
+                };
+                items.push((self.nodes_id_to_process_phase_id.get(node_id).copied(), item));
+            }
+        }
+        if self.configuration.align_depths {
+            // nodes are only aligned within their phase, a rank cannot span several clusters
+            let mut depths: BTreeMap<(Option<usize>, u32), Vec<String>> = BTreeMap::new();
+            for (node_id, _, depth) in nodes_discovery {
+                if let Some(depth) = depth {
+                    let phase_id = self.nodes_id_to_process_phase_id.get(node_id).copied();
+                    depths.entry((phase_id, *depth)).or_default().push(self.edge_endpoint(*node_id).0);
+                }
+            }
+            for ((phase_id, _), dot_ids) in depths.into_iter().filter(|(_, dot_ids)| dot_ids.len() > 1) {
+                items.push((phase_id, raw_dot_statement(format!("{{rank=same; {}}}", dot_ids.join("; ")))));
+            }
+        }
+        items
+    }
+
+    /**
+     * The DOT source of the graph so far, with the most recently added nodes highlighted.
+     * The frame is drawn from a copy of the graph, or from the .dot file when streaming,
+     * in which case the statements cannot be rewritten and the nodes are restated within their phase.
+     **/
+    fn frame_dot_string(&self) -> Result<String, LoggerError> {
+        let is_cluster = self.node_format() == GraphVizLoggerNodeFormat::AnchoredCluster;
+        let highlight = vec![
+            GraphvizNodeStyleItem::Color(GraphvizColor::red),
+            GraphvizNodeStyleItem::PenWidth(3),
+        ];
+        let discovery_items = self.discovery_items(&HashSet::new());
+        if self.dot_stream.is_some() {
+            let path = self.get_dot_file_path();
+            let mut dot = fs::read_to_string(&path)
+                .map_err(|e| LoggerError::io(format!("reading '{}'", path.display()), e))?;
+            let restated = self.snapshots_state.recent_nodes.iter().map(|node_id| {
+                let id = get_node_id("", *node_id);
+                let item = if is_cluster {
+                    GraphVizGraphItem::Cluster(GraphVizCluster::new(id, highlight.clone(), vec![], vec![]))
+                } else {
+                    GraphVizGraphItem::Node(GraphVizNode::new(id, highlight.clone()))
+                };
+                (self.nodes_id_to_process_phase_id.get(node_id).copied(), item)
+            });
+            for (phase_id, item) in restated.chain(discovery_items) {
+                dot.push_str(&format!("\n\t{}", self.restatement_in_phase(phase_id, item).to_dot_string()));
+            }
+            dot.push_str("\n}");
+            return Ok(dot);
+        }
+        let mut restyle = Restyle::default();
+        for node_id in &self.snapshots_state.recent_nodes {
+            restyle.items.insert(get_node_id("", *node_id), (highlight.clone(), is_cluster.then_some(*node_id)));
+        }
+        let mut frame = GraphVizDiGraph::new(self.graph.style.clone());
+        frame.items = self.graph.items.clone();
+        frame.edges = self.graph.edges.clone();
+        let mut phases_clusters = self.process_phases_clusters.clone();
+        for item in frame.items.iter_mut() {
+            restyle.apply_to_item(item);
+        }
+        for cluster in phases_clusters.values_mut() {
+            restyle.apply_to_cluster(cluster);
+        }
+        for (phase_id, item) in discovery_items {
+            match phase_id.and_then(|phase_id| phases_clusters.get_mut(&phase_id)) {
+                Some(cluster) => cluster.add_item(item),
+                None => frame.add_item(item),
+            }
+        }
+        let mut phases_clusters: Vec<(usize, GraphVizCluster)> = phases_clusters.into_iter().collect();
+        phases_clusters.sort_by_key(|(phase_id, _)| *phase_id);
+        for (_, cluster) in phases_clusters {
+            frame.add_item(GraphVizGraphItem::Cluster(cluster));
+        }
+        Ok(frame.to_dot_string())
+    }
+
+    /** Writes a frame of the graph so far, highlighting the most recently added nodes. **/
+    fn take_snapshot(&mut self, elapsed: Duration) -> Result<(), LoggerError> {
+        let Some(options) = self.configuration.snapshots.clone() else { return Ok(()) };
+        let dot = self.frame_dot_string()?;
+
+        let frames_folder = self.get_frames_folder();
+        let frame_name = self.snapshots_state.on_frame(elapsed);
+        let frame_path = frames_folder.join(format!("{}.dot", frame_name));
+        fs::write(&frame_path, dot)
+            .map_err(|e| LoggerError::io(format!("writing '{}'", frame_path.display()), e))?;
+        if options.render {
+            let output = render_dot_file(&frame_path, &self.configuration.output_format);
+            check_dot_output(&frame_name, output)?;
+        }
+        Ok(())
+    }
+
+    fn after_event(&mut self, meta: &EventMeta<Conf>) -> LoggerResult {
+        if let Some(options) = &self.configuration.snapshots {
+            if self.snapshots_state.on_event(meta.elapsed, options) {
+                self.take_snapshot(meta.elapsed)?;
+            }
+        }
+        Ok(LoggerControl::Continue)
+    }
+
+    /**
+     * Adds attributes to items and edges of the graph, at termination.
+     * The statements that have already been streamed cannot be rewritten: the items and edges are restated
+     * with the added attributes, the anchored clusters within their phase cluster.
+     **/
+    fn apply_restyle(&mut self, mut restyle: Restyle) -> Result<(), LoggerError> {
+        for item in self.graph.items.iter_mut() {
+            restyle.apply_to_item(item);
+        }
+        for edge in self.graph.edges.iter_mut() {
+            restyle.apply_to_edge(edge);
+        }
+        for cluster in self.process_phases_clusters.values_mut() {
+            restyle.apply_to_cluster(cluster);
+        }
+        if self.dot_stream.is_some() {
+            let mut streamed: Vec<_> = restyle.items
+                .into_iter()
+                .filter(|(id, _)| !restyle.restyled.contains(id))
+                .collect();
+            streamed.sort_by(|(id1, _), (id2, _)| id1.cmp(id2));
+            for (id, (added, cluster_node_id)) in streamed {
+                // restating a style (or a fill colour, printed with style=filled) replaces it,
+                // hence the kinds streamed so far are restated along with the added ones
+                let restates_style = added.iter().any(|item| {
+                    matches!(item, GraphvizNodeStyleItem::Style(_) | GraphvizNodeStyleItem::FillColor(_))
+                });
+                let kinds = self.streamed_style_kinds.entry(id.clone()).or_default();
+                let mut style = match restates_style && !kinds.is_empty() {
+                    true => vec![GraphvizNodeStyleItem::Style(kinds.clone())],
+                    false => vec![],
+                };
+                merge_node_style(&mut style, &added);
+                if let Some(GraphvizNodeStyleItem::Style(merged)) = style.last() {
+                    *kinds = merged.clone();
+                }
+                match cluster_node_id {
+                    Some(node_id) => {
+                        let phase_id = self.nodes_id_to_process_phase_id.get(&node_id).copied();
+                        let restated = GraphVizCluster::new(id, style, vec![], vec![]);
+                        self.emit_in_phase(phase_id, vec![GraphVizGraphItem::Cluster(restated)], vec![])?;
+                    }
+                    None => self.emit_item(GraphVizGraphItem::Node(GraphVizNode::new(id, style)))?,
+                }
+            }
+            let mut streamed: Vec<_> = restyle.edges
+                .into_iter()
+                .filter(|(ids, _)| !restyle.restyled_edges.contains(ids))
+                .collect();
+            streamed.sort_by(|(ids1, _), (ids2, _)| ids1.cmp(ids2));
+            for ((origin_id, target_id), style) in streamed {
+                self.emit_edge(GraphVizEdge::new(origin_id, None, target_id, None, style))?;
+            }
+        }
+        Ok(())
+    }
+
+    /** Adds the decorations given by the drawer to the drawn nodes, according to their exploration status. **/
+    fn decorate_nodes_by_status(&mut self) -> Result<(), LoggerError> {
+        let is_cluster = self.node_format() == GraphVizLoggerNodeFormat::AnchoredCluster;
+        let mut restyle = Restyle::default();
+        for (node_id, status) in &self.nodes_exploration_status {
+            if let Some(decoration) = self.drawer.node_status_decoration(*status) {
+                restyle.items.insert(get_node_id("", *node_id), (decoration, is_cluster.then_some(*node_id)));
+            }
+        }
+        if restyle.items.is_empty() {
+            return Ok(());
+        }
+        self.apply_restyle(restyle)
+    }
+
+    /** Colours the fills of the drawn nodes according to the heatmap metric, and adds a key of the colour ramp. **/
+    fn apply_heatmap(&mut self) -> Result<(), LoggerError> {
+        let Some(options) = self.configuration.heatmap.clone() else { return Ok(()) };
+        if options.ramp.is_empty() {
+            return Ok(());
+        }
+        let drawn_nodes = self.nodes_exploration_status.iter();
+        let values = match options.metric {
+            GraphVizNodeMetric::SubtreeSize => {
+                let own_values = drawn_nodes.map(|(node_id, _)| (*node_id, 1.0)).collect();
+                aggregate_over_subtrees(&own_values, self.step_classifier.tree_parents())
+            }
+            GraphVizNodeMetric::FilteredDescendants => {
+                let own_values = drawn_nodes
+                    .map(|(node_id, _)| (*node_id, self.heatmap_own_values.get(node_id).copied().unwrap_or(0.0)))
+                    .collect();
+                aggregate_over_subtrees(&own_values, self.step_classifier.tree_parents())
+            }
+            GraphVizNodeMetric::LeavesReached => {
+                let own_values = drawn_nodes
+                    .map(|(node_id, status)| {
+                        (*node_id, if *status == GraphVizNodeExplorationStatus::Leaf { 1.0 } else { 0.0 })
+                    })
+                    .collect();
+                aggregate_over_subtrees(&own_values, self.step_classifier.tree_parents())
+            }
+            GraphVizNodeMetric::Score => self.heatmap_own_values.clone(),
+        };
+        let Some(scale) = HeatmapScale::new(values.values(), options.logarithmic, options.ramp.len()) else {
+            return Ok(());
+        };
+
+        let is_cluster = self.node_format() == GraphVizLoggerNodeFormat::AnchoredCluster;
+        let mut restyle = Restyle::default();
+        for (node_id, value) in values.iter().filter(|(_, value)| value.is_finite()) {
+            let fill = vec![
+                GraphvizNodeStyleItem::FillColor(options.ramp[scale.ramp_index(*value)].clone()),
+                GraphvizNodeStyleItem::Style(vec![GvNodeStyleKind::Filled]),
+            ];
+            restyle.items.insert(get_node_id("", *node_id), (fill, is_cluster.then_some(*node_id)));
+        }
+        self.apply_restyle(restyle)?;
+
+        let key_items = (0..scale.key_len())
+            .map(|ramp_index| {
+                Box::new(GraphVizGraphItem::Node(GraphVizNode::new(
+                    format!("_heat{}", ramp_index),
+                    vec![
+                        GraphvizNodeStyleItem::Label(format!("≥ {}", format_metric_value(scale.lower_bound(ramp_index)))),
+                        GraphvizNodeStyleItem::Shape(GvNodeShape::Rectangle),
+                        GraphvizNodeStyleItem::FillColor(options.ramp[ramp_index].clone()),
+                        GraphvizNodeStyleItem::Style(vec![GvNodeStyleKind::Filled]),
+                    ],
+                )))
+            })
+            .collect();
+        let key = GraphVizCluster::new(
+            "_heatmap".to_string(),
+            vec![GraphvizNodeStyleItem::Label(format!("heatmap : {}", options.metric.describe()))],
+            key_items,
+            vec![],
+        );
+        self.emit_item(GraphVizGraphItem::Cluster(key))
+    }
+
+    /** Recolours and thickens the nodes and steps on the witness paths. **/
+    fn highlight_witness_paths(&mut self, paths: &WitnessPaths) -> Result<(), LoggerError> {
+        let color = self.drawer.witness_path_color();
+        let node_highlight = vec![GraphvizNodeStyleItem::Color(color.clone()), GraphvizNodeStyleItem::PenWidth(3)];
+        let edge_highlight = vec![
+            GraphvizEdgeStyleItem::Color(color),
+            GraphvizEdgeStyleItem::LineStyle(GvEdgeLineStyle::Bold),
+        ];
+        let is_cluster = self.node_format() == GraphVizLoggerNodeFormat::AnchoredCluster;
+        let mut restyle = Restyle::default();
+        for node_id in &paths.nodes {
+            restyle.items.insert(get_node_id("", *node_id), (node_highlight.clone(), is_cluster.then_some(*node_id)));
+        }
+        for (origin_node_id, target_node_id) in &paths.steps {
+            let (origin_id, _) = self.edge_endpoint(*origin_node_id);
+            let (target_id, _) = self.edge_endpoint(*target_node_id);
+            match self.configuration.step_format {
+                GraphVizLoggerStepFormat::StepNode => {
+                    let step_id = get_step_id("", *origin_node_id, *target_node_id);
+                    restyle.edges.insert((origin_id, step_id.clone()), edge_highlight.clone());
+                    restyle.edges.insert((step_id.clone(), target_id), edge_highlight.clone());
+                    restyle.items.insert(step_id, (node_highlight.clone(), None));
+                }
+                GraphVizLoggerStepFormat::EdgeLabel => {
+                    restyle.edges.insert((origin_id, target_id), edge_highlight.clone());
+                }
+            }
+        }
+        self.apply_restyle(restyle)
+    }
+
+    /**
+     * Adds a "+n more children" placeholder next to each drawn node some children of which are not drawn,
+     * and states in the legend (or in a note if there is none) how much has been left out.
+     **/
+    fn emit_truncation_summary(&mut self) -> Result<(), LoggerError> {
+        let hidden_children: Vec<(u32, u64)> = self.truncation.hidden_children
+            .iter()
+            .map(|(parent, count)| (*parent, *count))
+            .collect();
+        for (parent_node_id, count) in hidden_children {
+            let placeholder = GraphVizNode::new(
+                get_truncated_children_id("", parent_node_id),
+                vec![
+                    GraphvizNodeStyleItem::Label(
+                        format!("+{} more {}", count, if count == 1 { "child" } else { "children" })
+                    ),
+                    GraphvizNodeStyleItem::Shape(GvNodeShape::Rectangle),
+                    GraphvizNodeStyleItem::Style(vec![GvNodeStyleKind::Dashed]),
+                    GraphvizNodeStyleItem::Color(GraphvizColor::gray50),
+                    GraphvizNodeStyleItem::FontColor(GraphvizColor::gray50),
+                ],
+            );
+            let (origin_id, origin_cluster) = self.edge_endpoint(parent_node_id);
+            let edge = GraphVizEdge::new(
+                origin_id,
+                origin_cluster,
+                placeholder.id.clone(),
+                None,
+                vec![
+                    GraphvizEdgeStyleItem::Head(GvArrowHeadStyle::Vee(GvArrowHeadSide::Both)),
+                    GraphvizEdgeStyleItem::Color(GraphvizColor::gray50),
+                    GraphvizEdgeStyleItem::LineStyle(GvEdgeLineStyle::Dashed),
+                ],
+            );
+            self.emit_item(GraphVizGraphItem::Node(placeholder))?;
+            self.emit_edge(edge)?;
+        }
+        let summary = self.truncation.describe(&self.configuration.limits);
+        // restating the legend node with a longer label replaces its label
+        let summary_node = match &self.legend_node {
+            Some(legend_node) => {
+                let legend_label = legend_node.style.iter().find_map(|item| match item {
+                    GraphvizNodeStyleItem::Label(label) => Some(label.clone()),
+                    _ => None,
+                }).unwrap_or_default();
+                GraphVizNode::new(
+                    legend_node.id.clone(),
+                    vec![GraphvizNodeStyleItem::Label(format!("{}{}\\l", legend_label, summary))],
+                )
+            }
+            None => GraphVizNode::new(
+                "_truncation".to_string(),
+                vec![
+                    GraphvizNodeStyleItem::Label(summary),
+                    GraphvizNodeStyleItem::Shape(GvNodeShape::Rectangle),
+                    GraphvizNodeStyleItem::Style(vec![GvNodeStyleKind::Dashed]),
+                ],
+            ),
+        };
+        self.emit_item(GraphVizGraphItem::Node(summary_node))
+    }
+
+    /** Adds an item at the top level of the graph, or appends it to the .dot file when streaming. **/
+    fn emit_item(&mut self, item: GraphVizGraphItem) -> Result<(), LoggerError> {
+        if self.dot_stream.is_some() {
+            self.write_to_dot_stream(&format!("\n\t{}", item.to_dot_string()))
+        } else {
+            self.graph.add_item(item);
+            Ok(())
+        }
+    }
+
+    /** Adds an edge at the top level of the graph, or appends it to the .dot file when streaming. **/
+    fn emit_edge(&mut self, edge: GraphVizEdge) -> Result<(), LoggerError> {
+        if self.dot_stream.is_some() {
+            self.write_to_dot_stream(&format!("\n\t{}", edge.to_dot_string()))
+        } else {
+            self.graph.add_edge(edge);
+            Ok(())
+        }
+    }
+
+    /** An item restated within the subgraph of its phase, if any, so as not to be declared anew elsewhere. **/
+    fn restatement_in_phase(&self, phase_id: Option<usize>, item: GraphVizGraphItem) -> GraphVizGraphItem {
+        match phase_id {
+            Some(phase_id) => GraphVizGraphItem::Cluster(GraphVizCluster::new(
+                format!("phase{}", phase_id),
+                vec![],
+                vec![Box::new(item)],
+                vec![],
+            )),
+            None => item,
+        }
+    }
+
+    /**
+     * Adds items and edges to the cluster of a phase, or at the top level of the graph if there is none.
+     * When streaming, the subgraph of the phase is reopened, with its style the first time only,
+     * hence the clusters of the phases that are kept in memory remain empty.
+     **/
+    fn emit_in_phase(
+        &mut self,
+        phase_id: Option<usize>,
+        items: Vec<GraphVizGraphItem>,
+        edges: Vec<GraphVizEdge>,
+    ) -> Result<(), LoggerError> {
+        let Some(phase_id) = phase_id else {
+            for item in items {
+                self.emit_item(item)?;
+            }
+            for edge in edges {
+                self.emit_edge(edge)?;
+            }
+            return Ok(());
+        };
+        let is_new_phase = !self.process_phases_clusters.contains_key(&phase_id);
+        let phase_color = self.drawer.phase_color(phase_id);
+        let cluster = self.process_phases_clusters.entry(phase_id).or_insert_with(|| {
+            GraphVizCluster::new(
+                format!("phase{}", phase_id),
+                vec![GraphvizNodeStyleItem::FillColor(phase_color)],
+                vec![],
+                vec![],
+            )
+        });
+        if self.dot_stream.is_none() {
+            cluster.items.extend(items.into_iter().map(Box::new));
+            cluster.edges.extend(edges);
+            return Ok(());
+        }
+        let style = if is_new_phase { cluster.style.clone() } else { vec![] };
+        let reopened = GraphVizCluster::new(cluster.id.clone(), style, items.into_iter().map(Box::new).collect(), edges);
+        self.write_to_dot_stream(&format!("\n\t{}", reopened.to_dot_string()))
+    }
+}
+
+
+// integers are written without decimals
+fn format_metric_value(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        format!("{:.2}", value)
+    }
+}
+
+
+/** Attributes to add at termination to some items and edges of the graph, by DOT id. **/
+#[derive(Default)]
+struct Restyle {
+    // id → attributes, and the process node if the item is its anchored cluster
+    items: HashMap<String, (Vec<GraphvizNodeStyleItem>, Option<u32>)>,
+    // (origin id, target id) → attributes, which replace those of the same kind
+    edges: HashMap<(String, String), Vec<GraphvizEdgeStyleItem>>,
+    // ids of the items and edges found and restyled
+    restyled: HashSet<String>,
+    restyled_edges: HashSet<(String, String)>,
+}
+
+impl Restyle {
+
+    fn apply_to_item(&mut self, item: &mut GraphVizGraphItem) {
+        match item {
+            GraphVizGraphItem::Node(node) => {
+                if let Some((style, _)) = self.items.get(&node.id) {
+                    merge_node_style(&mut node.style, style);
+                    self.restyled.insert(node.id.clone());
+                }
+            }
+            GraphVizGraphItem::Cluster(cluster) => {
+                if let Some((style, _)) = self.items.get(&cluster.id) {
+                    merge_node_style(&mut cluster.style, style);
+                    self.restyled.insert(cluster.id.clone());
+                } else {
+                    self.apply_to_cluster(cluster);
+                }
+            }
+        }
+    }
+
+    fn apply_to_cluster(&mut self, cluster: &mut GraphVizCluster) {
+        for item in cluster.items.iter_mut() {
+            self.apply_to_item(item);
+        }
+        for edge in cluster.edges.iter_mut() {
+            self.apply_to_edge(edge);
+        }
+    }
+
+    fn apply_to_edge(&mut self, edge: &mut GraphVizEdge) {
+        let ids = (edge.origin_node_id.clone(), edge.target_node_id.clone());
+        if let Some(style) = self.edges.get(&ids) {
+            merge_edge_style(&mut edge.style, style);
+            self.restyled_edges.insert(ids);
+        }
+    }
+}
 
 
 impl<Conf: AbstractProcessConfiguration + 'static> AbstractProcessLogger<Conf>
     for GenericGraphVizLogger<Conf>
 {
-    fn log_initialize_process(&mut self, manager: &GenericProcessManager<Conf>) {
-        let _ = fs::remove_dir_all(&self.configuration.temp_folder);
-        let _ = fs::create_dir_all(&self.configuration.temp_folder);
-        if !self.configuration.parent_folder.is_empty() {
-            let _ = fs::create_dir_all(&self.configuration.parent_folder);
+    fn log_initialize_process(&mut self, manager: &GenericProcessManager<Conf>) -> LoggerResult {
+        reset_folder(&self.configuration.temp_folder)?;
+        create_folder(&self.configuration.parent_folder)?;
+        if self.configuration.streaming {
+            self.start_dot_stream()?;
+        }
+        if self.configuration.snapshots.is_some() {
+            let frames_folder = self.get_frames_folder();
+            reset_folder(&frames_folder.to_string_lossy())?;
         }
         if let Some(legend) = &self.legend_writer {
             let legend_node = legend.get_legend_node(
@@ -51,16 +669,48 @@ impl<Conf: AbstractProcessConfiguration + 'static> AbstractProcessLogger<Conf>
                 manager.get_filters_manager(),
                 manager.is_memoized(),
             );
-            self.graph.add_node(legend_node);
+            self.legend_node = Some(legend_node.clone());
+            self.emit_item(GraphVizGraphItem::Node(legend_node))?;
         }
+        Ok(LoggerControl::Continue)
     }
 
     fn log_new_node(
         &mut self,
         ctx: &Conf::ContextAndParameterization,
+        meta: &EventMeta<Conf>,
         new_node_id: u32,
         new_node: &Conf::DomainSpecificNode,
-    ) {
+    ) -> LoggerResult {
+        if !self.configuration.limits.is_unlimited() {
+            let limits = self.configuration.limits;
+            if let Err(drawn_parent) = self.truncation.on_new_node(&limits, new_node_id, meta.depth, meta.parent_node_id) {
+                // the placeholder of the parent is attached to it, hence it cannot be collapsed
+                if let (Some(parent_node_id), true) = (drawn_parent, self.configuration.collapse_linear_chains) {
+                    self.filtered_nodes.insert(parent_node_id);
+                }
+                return self.after_event(meta);
+            }
+        }
+        if self.initial_node_id.is_none() {
+            self.initial_node_id = Some(new_node_id);
+        }
+        self.nodes_exploration_status.insert(new_node_id, GraphVizNodeExplorationStatus::Frontier);
+        self.step_classifier.on_new_node(new_node_id);
+        if self.configuration.annotate_discovery || self.configuration.align_depths {
+            self.nodes_discovery.push((new_node_id, meta.discovery_index, meta.depth));
+        }
+        if let Some(GraphVizNodeMetric::Score) = self.configuration.heatmap.as_ref().map(|options| options.metric) {
+            if let Some(score) = self.drawer.node_score(ctx, new_node) {
+                self.heatmap_own_values.insert(new_node_id, score);
+            }
+        }
+        if self.drawer.is_witness_target(ctx, new_node) {
+            self.witness_targets.push(new_node_id);
+        }
+        if let Some(options) = &self.configuration.snapshots {
+            self.snapshots_state.on_new_node(new_node_id, options);
+        }
         let as_gv_item = match self.node_format() {
             GraphVizLoggerNodeFormat::AnchoredCluster => {
                 let cluster_name = get_node_id("", new_node_id);
@@ -113,114 +763,123 @@ impl<Conf: AbstractProcessConfiguration + 'static> AbstractProcessLogger<Conf>
             }
         };
 
-        if let Some(phase_id) = self.drawer.node_phase(ctx, new_node) {
-            if let std::collections::hash_map::Entry::Vacant(e) =
-                self.process_phases_clusters.entry(phase_id)
-            {
-                let phase_style =
-                    vec![GraphvizNodeStyleItem::FillColor(self.drawer.phase_color(phase_id))];
-                e.insert(GraphVizCluster::new(
-                    format!("phase{}", phase_id),
-                    phase_style,
-                    vec![],
-                    vec![],
-                ));
-            }
-            self.process_phases_clusters
-                .get_mut(&phase_id)
-                .unwrap()
-                .items
-                .push(Box::new(as_gv_item));
+        if self.dot_stream.is_some() {
+            let (id, style) = match &as_gv_item {
+                GraphVizGraphItem::Node(node) => (&node.id, &node.style),
+                GraphVizGraphItem::Cluster(cluster) => (&cluster.id, &cluster.style),
+            };
+            let kinds = style.iter().fold(vec![], |mut kinds, item| {
+                if let GraphvizNodeStyleItem::Style(item_kinds) = item {
+                    kinds.extend(item_kinds.iter().cloned());
+                }
+                kinds
+            });
+            self.streamed_style_kinds.insert(id.clone(), kinds);
+        }
+        let phase_id = self.drawer.node_phase(ctx, new_node);
+        if let Some(phase_id) = phase_id {
             self.nodes_id_to_process_phase_id.insert(new_node_id, phase_id);
-        } else {
-            match as_gv_item {
-                GraphVizGraphItem::Node(n)    => self.graph.add_node(n),
-                GraphVizGraphItem::Cluster(c) => self.graph.add_cluster(c),
-            }
         }
+        self.emit_in_phase(phase_id, vec![as_gv_item], vec![])?;
+        self.after_event(meta)
     }
 
     fn log_new_step(
         &mut self,
         ctx: &Conf::ContextAndParameterization,
+        meta: &EventMeta<Conf>,
         origin_node_id: u32,
         step: &Conf::DomainSpecificStep,
         target_node_id: u32,
         _target_node: &Conf::DomainSpecificNode,
-    ) {
+    ) -> LoggerResult {
+        if self.truncation.is_hidden(origin_node_id) || self.truncation.is_hidden(target_node_id) {
+            self.truncation.hidden_steps += 1;
+            return self.after_event(meta);
+        }
+        // when streaming, the witness paths are found in the tree of the steps that discovered the nodes
+        if self.dot_stream.is_none() {
+            self.process_steps.push((origin_node_id, target_node_id));
+        }
         let step_name = get_step_id("", origin_node_id, target_node_id);
         let img_path: PathBuf =
             [&self.configuration.temp_folder, &format!("{}.png", step_name)]
                 .iter()
                 .collect();
-        let step_style = self.drawer.draw_step(ctx, step, &img_path).to_graphviz_node_styte(&img_path);
         let edge_color = self.drawer.step_edge_color(ctx, step);
-
-        let step_node = GraphVizNode::new(step_name, step_style);
-        let edge_opts = vec![
+        let mut edge_opts = vec![
             GraphvizEdgeStyleItem::Head(GvArrowHeadStyle::Vee(GvArrowHeadSide::Both)),
             GraphvizEdgeStyleItem::Color(edge_color),
         ];
-        let (tran_to_step, tran_to_new) = match self.node_format() {
-            GraphVizLoggerNodeFormat::AnchoredCluster => (
-                GraphVizEdge::new(
-                    get_anchor_id("", origin_node_id),
-                    Some(get_node_id("", origin_node_id)),
-                    step_node.id.clone(),
-                    None,
-                    edge_opts.clone(),
-                ),
-                GraphVizEdge::new(
-                    step_node.id.clone(),
-                    None,
-                    get_anchor_id("", target_node_id),
-                    Some(get_node_id("", target_node_id)),
-                    edge_opts,
-                ),
-            ),
-            GraphVizLoggerNodeFormat::SimpleNode => (
-                GraphVizEdge::new(
-                    get_node_id("", origin_node_id),
-                    None,
-                    step_node.id.clone(),
-                    None,
-                    edge_opts.clone(),
-                ),
-                GraphVizEdge::new(
-                    step_node.id.clone(),
-                    None,
-                    get_node_id("", target_node_id),
-                    None,
-                    edge_opts,
-                ),
-            ),
+        let step_kind = self.step_classifier.classify(origin_node_id, target_node_id);
+        merge_edge_style(&mut edge_opts, &self.drawer.step_edge_kind_style(ctx, step, step_kind));
+        let (origin_id, origin_cluster) = self.edge_endpoint(origin_node_id);
+        let (target_id, target_cluster) = self.edge_endpoint(target_node_id);
+
+        let (step_node, edges) = match self.configuration.step_format {
+            GraphVizLoggerStepFormat::StepNode => {
+                let step_style = self.drawer.draw_step(ctx, step, &img_path);
+                let step_node = GraphVizNode::new(step_name, step_style.to_graphviz_node_styte(&img_path));
+                let tran_to_step = GraphVizEdge::new(
+                    origin_id, origin_cluster, step_node.id.clone(), None, edge_opts.clone(),
+                );
+                let tran_to_new = GraphVizEdge::new(
+                    step_node.id.clone(), None, target_id, target_cluster, edge_opts,
+                );
+                (Some(step_node), vec![tran_to_step, tran_to_new])
+            }
+            GraphVizLoggerStepFormat::EdgeLabel => {
+                match self.drawer.step_label_text(ctx, step) {
+                    Some(text) => edge_opts.push(GraphvizEdgeStyleItem::Label(text)),
+                    None => match self.drawer.draw_step(ctx, step, &img_path) {
+                        BuiltinGraphvizLoggerItemStyle::Default(text_style) => {
+                            edge_opts.push(GraphvizEdgeStyleItem::Label(text_style.label));
+                            edge_opts.push(GraphvizEdgeStyleItem::FontColor(text_style.font_color));
+                            edge_opts.push(GraphvizEdgeStyleItem::FontSize(text_style.font_size));
+                        }
+                        // an image cannot be drawn on an edge
+                        BuiltinGraphvizLoggerItemStyle::CustomImage => {
+                            edge_opts.push(GraphvizEdgeStyleItem::Label(format!("{} → {}", origin_node_id, target_node_id)));
+                        }
+                    },
+                }
+                let transition = GraphVizEdge::new(
+                    origin_id, origin_cluster, target_id, target_cluster, edge_opts,
+                );
+                (None, vec![transition])
+            }
         };
 
-        match (
+        let phase_id = match (
             self.nodes_id_to_process_phase_id.get(&origin_node_id),
             self.nodes_id_to_process_phase_id.get(&target_node_id),
         ) {
-            (Some(orig_phase), Some(targ_phase)) if orig_phase == targ_phase => {
-                let cluster = self.process_phases_clusters.get_mut(orig_phase).unwrap();
-                cluster.add_node(step_node);
-                cluster.add_edge(tran_to_step);
-                cluster.add_edge(tran_to_new);
-            }
-            _ => {
-                self.graph.add_node(step_node);
-                self.graph.add_edge(tran_to_step);
-                self.graph.add_edge(tran_to_new);
-            }
-        }
+            (Some(orig_phase), Some(targ_phase)) if orig_phase == targ_phase => Some(*orig_phase),
+            _ => None,
+        };
+        let step_items = step_node.into_iter().map(GraphVizGraphItem::Node).collect();
+        self.emit_in_phase(phase_id, step_items, edges)?;
+        self.after_event(meta)
     }
 
     fn log_filtered(
         &mut self,
         ctx: &Conf::ContextAndParameterization,
+        meta: &EventMeta<Conf>,
         parent_node_id: u32,
         filtration_result: &Conf::FiltrationResult,
-    ) {
+    ) -> LoggerResult {
+        if self.truncation.is_hidden(parent_node_id) {
+            self.truncation.hidden_filtrations += 1;
+            return self.after_event(meta);
+        }
         self.filtration_counter += 1;
+        if let Some(GraphVizNodeMetric::FilteredDescendants) = self.configuration.heatmap.as_ref().map(|options| options.metric) {
+            *self.heatmap_own_values.entry(parent_node_id).or_default() += 1.0;
+        }
+        if self.configuration.collapse_linear_chains {
+            self.filtered_nodes.insert(parent_node_id);
+        }
         let filter_name = get_filtration_id("", self.filtration_counter);
         let img_path: PathBuf =
             [&self.configuration.temp_folder, &format!("{}.png", filter_name)]
@@ -254,31 +913,112 @@ impl<Conf: AbstractProcessConfiguration + 'static> AbstractProcessLogger<Conf>
             ),
         };
 
-        if let Some(phase_id) = self.nodes_id_to_process_phase_id.get(&parent_node_id) {
-            let cluster = self.process_phases_clusters.get_mut(phase_id).unwrap();
-            cluster.add_node(filter_node);
-            cluster.add_edge(elim_edge);
-        } else {
-            self.graph.add_node(filter_node);
-            self.graph.add_edge(elim_edge);
+        let phase_id = self.nodes_id_to_process_phase_id.get(&parent_node_id).copied();
+        self.emit_in_phase(phase_id, vec![GraphVizGraphItem::Node(filter_node)], vec![elim_edge])?;
+        self.after_event(meta)
+    }
+
+    fn log_all_children_processed(
+        &mut self,
+        _ctx: &Conf::ContextAndParameterization,
+        meta: &EventMeta<Conf>,
+        parent_node_id: u32,
+    ) -> LoggerResult {
+        if let Some(status) = self.nodes_exploration_status.get_mut(&parent_node_id) {
+            *status = GraphVizNodeExplorationStatus::Expanded;
         }
+        self.after_event(meta)
     }
 
-    fn log_terminate_process(&mut self, manager: &GenericProcessManager<Conf>) {
-        for (_, cluster) in self.process_phases_clusters.drain() {
-            self.graph.add_cluster(cluster);
+    fn log_notify_node_without_children(
+        &mut self,
+        _ctx: &Conf::ContextAndParameterization,
+        meta: &EventMeta<Conf>,
+        node_id: u32,
+    ) -> LoggerResult {
+        if let Some(status) = self.nodes_exploration_status.get_mut(&node_id) {
+            *status = GraphVizNodeExplorationStatus::Leaf;
         }
-        if let Some(legend) = &self.legend_writer {
-            self.graph.add_node(
-                legend.get_verdict_node(&manager.context_and_param, &manager.global_state),
-            );
+        self.after_event(meta)
+    }
+
+    fn log_terminate_process(
+        &mut self,
+        manager: &GenericProcessManager<Conf>,
+        _reason: &TerminationReason,
+    ) -> LoggerResult {
+        if let Some(options) = self.configuration.snapshots.clone() {
+            if self.snapshots_state.events_since_last_frame > 0 || self.snapshots_state.frames.is_empty() {
+                let elapsed = self.snapshots_state.last_frame_elapsed;
+                self.take_snapshot(elapsed)?;
+            }
+            if options.html_player {
+                let frame_files: Vec<String> = self.snapshots_state.frames
+                    .iter()
+                    .map(|frame| format!("{}.{}", frame, self.configuration.output_format))
+                    .collect();
+                let player_path = self.get_frames_folder().join("player.html");
+                fs::write(&player_path, html_player(&frame_files))
+                    .map_err(|e| LoggerError::io(format!("writing '{}'", player_path.display()), e))?;
+            }
         }
-        if let Err(e) = self.graph.print_dot(
-            &[self.configuration.parent_folder.clone()],
-            &self.configuration.output_file_name,
-            &self.configuration.output_format,
-        ) {
-            println!("error during logger termination : {:?}", e);
+        let witness_paths = match self.initial_node_id {
+            Some(initial_node_id) if !self.witness_targets.is_empty() => {
+                if self.dot_stream.is_some() {
+                    let tree_steps: Vec<(u32, u32)> = self.step_classifier.tree_parents()
+                        .iter()
+                        .map(|(node_id, parent)| (*parent, *node_id))
+                        .collect();
+                    Some(find_witness_paths(&tree_steps, initial_node_id, &self.witness_targets))
+                } else {
+                    Some(find_witness_paths(&self.process_steps, initial_node_id, &self.witness_targets))
+                }
+            }
+            _ => None,
+        };
+        let mut collapsed = HashSet::new();
+        // what has been streamed cannot be rewritten
+        if self.configuration.collapse_linear_chains && self.dot_stream.is_none() {
+            // the nodes on witness paths are kept, as are filtered nodes
+            if let Some(paths) = &witness_paths {
+                self.filtered_nodes.extend(paths.nodes.iter().copied());
+            }
+            collapsed = self.collapse_linear_chains();
         }
+        self.apply_heatmap()?;
+        self.decorate_nodes_by_status()?;
+        if let Some(paths) = witness_paths {
+            self.highlight_witness_paths(&paths)?;
+        }
+        for (phase_id, item) in self.discovery_items(&collapsed) {
+            self.emit_in_phase(phase_id, vec![item], vec![])?;
+        }
+        // when streaming, the clusters of the phases have already been written
+        let clusters: Vec<GraphVizCluster> = self.process_phases_clusters.drain().map(|(_, cluster)| cluster).collect();
+        if self.dot_stream.is_none() {
+            for cluster in clusters {
+                self.emit_item(GraphVizGraphItem::Cluster(cluster))?;
+            }
+        }
+        if self.truncation.is_truncated() {
+            self.emit_truncation_summary()?;
+        }
+        if let Some(legend) = &self.legend_writer {
+            let verdict_node = legend.get_verdict_node(&manager.context_and_param, &manager.global_state);
+            self.emit_item(GraphVizGraphItem::Node(verdict_node))?;
+        }
+        let output = if self.dot_stream.is_some() {
+            self.write_to_dot_stream("\n}")?;
+            self.dot_stream = None;
+            render_dot_file(&self.get_dot_file_path(), &self.configuration.output_format)
+        } else {
+            self.graph.print_dot(
+                std::slice::from_ref(&self.configuration.parent_folder),
+                &self.configuration.output_file_name,
+                &self.configuration.output_format,
+            )
+        };
+        check_dot_output(&self.configuration.output_file_name, output)?;
+        Ok(LoggerControl::Continue)
     }
 }
@@ -0,0 +1,123 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+
+/**
+ * Limits on the size of the graph drawn by the GenericGraphVizLogger.
+ *
+ * A node beyond the limits is not drawn (draw_node_view is not called), nor are its descendants
+ * and the steps and filtrations that involve them.
+ * The children that are not drawn are counted in a "+n more children" placeholder
+ * next to their drawn parent, and the legend states how much has been left out.
+ **/
+#[derive(Clone, Copy, PartialEq, Debug, Eq, Default)]
+pub struct GraphVizOutputLimits {
+    /** Maximum number of process nodes drawn. **/
+    pub max_nodes: Option<usize>,
+    /** Maximum depth of the process nodes drawn, the initial node being at depth 0. **/
+    pub max_depth: Option<u32>,
+    /** Maximum number of children drawn for each node. **/
+    pub max_children: Option<usize>,
+}
+
+impl GraphVizOutputLimits {
+    pub(crate) fn is_unlimited(&self) -> bool {
+        self.max_nodes.is_none() && self.max_depth.is_none() && self.max_children.is_none()
+    }
+
+    pub(crate) fn describe(&self) -> String {
+        let mut limits = vec![];
+        if let Some(max_nodes) = self.max_nodes {
+            limits.push(format!("max_nodes={}", max_nodes));
+        }
+        if let Some(max_depth) = self.max_depth {
+            limits.push(format!("max_depth={}", max_depth));
+        }
+        if let Some(max_children) = self.max_children {
+            limits.push(format!("max_children={}", max_children));
+        }
+        limits.join(", ")
+    }
+}
+
+
+/** What has been left out of the graph because of the GraphVizOutputLimits. **/
+#[derive(Default)]
+pub(crate) struct TruncationState {
+    pub shown_nodes: usize,
+    pub hidden_nodes: HashSet<u32>,
+    // drawn node → number of its drawn children
+    pub shown_children: HashMap<u32, usize>,
+    // drawn node → number of its children that are not drawn
+    pub hidden_children: BTreeMap<u32, u64>,
+    pub hidden_steps: u64,
+    pub hidden_filtrations: u64,
+}
+
+impl TruncationState {
+
+    /**
+     * Decides whether a new node is drawn and accounts for it.
+     * Returns the drawn parent of the node if it is not drawn.
+     **/
+    pub fn on_new_node(
+        &mut self,
+        limits: &GraphVizOutputLimits,
+        node_id: u32,
+        depth: Option<u32>,
+        parent_node_id: Option<u32>,
+    ) -> Result<(), Option<u32>> {
+        let parent_is_hidden = parent_node_id.is_some_and(|parent| self.hidden_nodes.contains(&parent));
+        let is_hidden = parent_is_hidden
+            || limits.max_nodes.is_some_and(|max| self.shown_nodes >= max)
+            || limits.max_depth.is_some_and(|max| depth.is_some_and(|depth| depth > max))
+            || limits.max_children.is_some_and(|max| {
+                parent_node_id.is_some_and(|parent| self.shown_children.get(&parent).copied().unwrap_or(0) >= max)
+            });
+        if !is_hidden {
+            self.shown_nodes += 1;
+            if let Some(parent) = parent_node_id {
+                *self.shown_children.entry(parent).or_default() += 1;
+            }
+            return Ok(());
+        }
+        self.hidden_nodes.insert(node_id);
+        match parent_node_id {
+            Some(parent) if !parent_is_hidden => {
+                *self.hidden_children.entry(parent).or_default() += 1;
+                Err(Some(parent))
+            }
+            _ => Err(None),
+        }
+    }
+
+    pub fn is_hidden(&self, node_id: u32) -> bool {
+        self.hidden_nodes.contains(&node_id)
+    }
+
+    pub fn is_truncated(&self) -> bool {
+        !self.hidden_nodes.is_empty()
+    }
+
+    pub fn describe(&self, limits: &GraphVizOutputLimits) -> String {
+        format!(
+            "truncated ({}) : {} nodes, {} steps and {} filtrations not shown",
+            limits.describe(), self.hidden_nodes.len(), self.hidden_steps, self.hidden_filtrations
+        )
+    }
+}
@@ -14,15 +14,25 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
 
 use graph_process_manager_core::process::config::AbstractProcessConfiguration;
 use graphviz_dot_builder::graph::graph::GraphVizDiGraph;
 use graphviz_dot_builder::graph::style::{GraphvizGraphStyleItem, GvGraphRankDir};
 use graphviz_dot_builder::item::cluster::GraphVizCluster;
+use graphviz_dot_builder::item::node::node::GraphVizNode;
+use graphviz_dot_builder::item::node::style::GvNodeStyle;
 use graphviz_dot_builder::traits::GraphVizOutputFormat;
 
-use crate::graphviz::format::{GraphVizLoggerNodeFormat, GraphVizProcessLoggerLayout};
+use crate::graphviz::format::{GraphVizLoggerNodeFormat, GraphVizLoggerStepFormat, GraphVizNodeExplorationStatus, GraphVizProcessLoggerLayout};
+use crate::graphviz::edges::StepClassifier;
+use crate::graphviz::heatmap::GraphVizHeatmapOptions;
+use crate::graphviz::limits::{GraphVizOutputLimits, TruncationState};
+use crate::graphviz::snapshot::{GraphVizSnapshotOptions, SnapshotsState};
+use crate::logger_registry::LoggerWithProduct;
 
 use super::drawers::drawer::GraphVizProcessDrawer;
 use super::drawers::legend_writer::ProcessLegendWriter;
@@ -33,6 +43,40 @@ pub struct GenericGraphVizLoggerConfiguration {
     pub temp_folder: String,
     pub parent_folder: String,
     pub output_file_name: String,
+    /**
+     * Whether to append the DOT statements to the .dot file as events arrive
+     * instead of keeping the whole graph in memory (false by default).
+     * The subgraphs of the phases are reopened to add to them, and the graph is strict so that
+     * the nodes and edges restyled at termination (e.g. on witness paths) can be restated.
+     * Witness paths then follow the steps through which the nodes were discovered,
+     * and the verdict and rendering are still done at termination.
+     **/
+    pub streaming: bool,
+    /** How the steps are drawn (StepNode by default). **/
+    pub step_format: GraphVizLoggerStepFormat,
+    /**
+     * Whether to collapse, at termination, the chains of nodes that each have exactly one parent and one child
+     * into a single "n steps elided" edge (false by default).
+     * The ends of the chains, filtered nodes and nodes in a different phase from their neighbours are kept.
+     * Not available in streaming mode.
+     **/
+    pub collapse_linear_chains: bool,
+    /**
+     * Whether to annotate each node with its discovery index and depth (false by default),
+     * as an external label of simple nodes or as the label of anchored clusters.
+     **/
+    pub annotate_discovery: bool,
+    /**
+     * Whether to align the nodes of equal depth with rank=same subgraphs (false by default).
+     * GraphViz may not align nodes that are inside clusters (phases or anchored clusters).
+     **/
+    pub align_depths: bool,
+    /** Periodic snapshots of the graph, for animations (None by default). **/
+    pub snapshots: Option<GraphVizSnapshotOptions>,
+    /** Colouring of the node fills according to a metric computed at termination (None by default). **/
+    pub heatmap: Option<GraphVizHeatmapOptions>,
+    /** Caps on the size of the graph, beyond which nodes are summarized (unlimited by default). **/
+    pub limits: GraphVizOutputLimits,
 }
 
 impl GenericGraphVizLoggerConfiguration {
@@ -42,7 +86,20 @@ impl GenericGraphVizLoggerConfiguration {
         parent_folder: String,
         output_file_name: String,
     ) -> Self {
-        Self { output_format, temp_folder, parent_folder, output_file_name }
+        Self {
+            output_format,
+            temp_folder,
+            parent_folder,
+            output_file_name,
+            streaming: false,
+            step_format: GraphVizLoggerStepFormat::StepNode,
+            collapse_linear_chains: false,
+            annotate_discovery: false,
+            align_depths: false,
+            snapshots: None,
+            heatmap: None,
+            limits: GraphVizOutputLimits::default(),
+        }
     }
 }
 
@@ -52,11 +109,31 @@ pub struct GenericGraphVizLogger<Conf: AbstractProcessConfiguration> {
     pub drawer: Box<dyn GraphVizProcessDrawer<Conf>>,
     /// When `Some`, a legend node and a verdict node are added to the graph.
     pub legend_writer: Option<Box<dyn ProcessLegendWriter<Conf>>>,
-    // internal graph being built
+    // internal graph being built, which only keeps its style in streaming mode
     pub graph: GraphVizDiGraph,
+    // the .dot file, while streaming
+    pub(crate) dot_stream: Option<BufWriter<File>>,
+    pub(crate) snapshots_state: SnapshotsState,
+    pub(crate) truncation: TruncationState,
+    // the legend node as first drawn, restated at termination if the graph is truncated
+    pub(crate) legend_node: Option<GraphVizNode>,
     pub(crate) nodes_id_to_process_phase_id: HashMap<u32, usize>,
     pub(crate) process_phases_clusters: HashMap<usize, GraphVizCluster>,
     pub(crate) filtration_counter: u32,
+    // structure of the process, kept to collapse linear chains and to find witness paths (steps not kept while streaming)
+    pub(crate) initial_node_id: Option<u32>,
+    pub(crate) process_steps: Vec<(u32, u32)>,
+    pub(crate) filtered_nodes: HashSet<u32>,
+    pub(crate) witness_targets: Vec<u32>,
+    // (node id, discovery index, depth) of the drawn nodes, for annotate_discovery and align_depths
+    pub(crate) nodes_discovery: Vec<(u32, Option<u32>, Option<u32>)>,
+    pub(crate) step_classifier: StepClassifier,
+    // scores or numbers of filtrations of the drawn nodes, for the heatmap
+    pub(crate) heatmap_own_values: HashMap<u32, f64>,
+    // of the drawn nodes
+    pub(crate) nodes_exploration_status: HashMap<u32, GraphVizNodeExplorationStatus>,
+    // DOT id → kinds of the style of the drawn nodes, while streaming, so that restating a style extends it
+    pub(crate) streamed_style_kinds: HashMap<String, GvNodeStyle>,
 }
 
 impl<Conf: AbstractProcessConfiguration> GenericGraphVizLogger<Conf> {
@@ -75,12 +152,39 @@ impl<Conf: AbstractProcessConfiguration> GenericGraphVizLogger<Conf> {
             drawer,
             legend_writer,
             graph: GraphVizDiGraph::new(vec![GraphvizGraphStyleItem::Rankdir(rankdir)]),
+            dot_stream: None,
+            snapshots_state: SnapshotsState::default(),
+            truncation: TruncationState::default(),
+            legend_node: None,
             nodes_id_to_process_phase_id: HashMap::new(),
             process_phases_clusters: HashMap::new(),
             filtration_counter: 0,
+            initial_node_id: None,
+            process_steps: vec![],
+            filtered_nodes: HashSet::new(),
+            witness_targets: vec![],
+            nodes_discovery: vec![],
+            step_classifier: StepClassifier::default(),
+            heatmap_own_values: HashMap::new(),
+            nodes_exploration_status: HashMap::new(),
+            streamed_style_kinds: HashMap::new(),
         }
     }
 
+    pub fn get_dot_file_path(&self) -> PathBuf {
+        [
+            &self.configuration.parent_folder,
+            &format!("{}.dot", self.configuration.output_file_name),
+        ].iter().collect()
+    }
+
+    pub fn get_frames_folder(&self) -> PathBuf {
+        [
+            &self.configuration.parent_folder,
+            &format!("{}_frames", self.configuration.output_file_name),
+        ].iter().collect()
+    }
+
     pub(crate) fn node_format(&self) -> GraphVizLoggerNodeFormat {
         if self.drawer.node_view_count() <= 1 {
             GraphVizLoggerNodeFormat::SimpleNode
@@ -89,3 +193,16 @@ impl<Conf: AbstractProcessConfiguration> GenericGraphVizLogger<Conf> {
         }
     }
 }
+
+
+/**
+ * The product is the graph as completed by log_terminate_process.
+ * In streaming mode, it only has the style of the graph.
+ **/
+impl<Conf: AbstractProcessConfiguration> LoggerWithProduct for GenericGraphVizLogger<Conf> {
+    type Product = GraphVizDiGraph;
+
+    fn into_product(self) -> GraphVizDiGraph {
+        self.graph
+    }
+}
@@ -14,10 +14,16 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+pub(crate) mod chains;
+pub(crate) mod edges;
 pub mod format;
+pub mod heatmap;
 mod implem;
 pub mod item;
+pub mod limits;
 pub mod logger;
+pub mod snapshot;
 pub(crate) mod util;
+pub(crate) mod witness;
 
 pub mod drawers;
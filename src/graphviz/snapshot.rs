@@ -0,0 +1,164 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::time::Duration;
+
+
+/**
+ * Periodic snapshots of the graph being built by the GenericGraphVizLogger,
+ * to be assembled as the frames of an animation of the exploration.
+ *
+ * A snapshot is taken once every_events events have been logged since the previous one,
+ * or once every_duration has elapsed since the previous one, and a last one at termination.
+ * Frames are written in "{parent_folder}/{output_file_name}_frames" as "frame_00000.dot", "frame_00001.dot"...
+ **/
+#[derive(Clone, PartialEq, Debug, Eq)]
+pub struct GraphVizSnapshotOptions {
+    pub every_events: Option<u64>,
+    pub every_duration: Option<Duration>,
+    /** How many of the most recently added nodes are highlighted in each frame. **/
+    pub highlighted_nodes: usize,
+    /** Whether to render each frame with dot, in the output format of the logger. **/
+    pub render: bool,
+    /**
+     * Whether to write a "player.html" page next to the frames to step through them offline.
+     * It shows the rendered frames, hence requires render.
+     **/
+    pub html_player: bool,
+}
+
+impl GraphVizSnapshotOptions {
+    pub fn every_events(every_events: u64) -> Self {
+        Self {
+            every_events: Some(every_events),
+            every_duration: None,
+            highlighted_nodes: 1,
+            render: true,
+            html_player: false,
+        }
+    }
+
+    pub fn every_duration(every_duration: Duration) -> Self {
+        Self {
+            every_events: None,
+            every_duration: Some(every_duration),
+            highlighted_nodes: 1,
+            render: true,
+            html_player: false,
+        }
+    }
+}
+
+
+/** Progress of the snapshots of a logger. **/
+#[derive(Default)]
+pub(crate) struct SnapshotsState {
+    pub events_since_last_frame: u64,
+    pub last_frame_elapsed: Duration,
+    pub recent_nodes: VecDeque<u32>,
+    /** Names of the frames taken so far, without extension. **/
+    pub frames: Vec<String>,
+}
+
+impl SnapshotsState {
+
+    pub fn on_new_node(&mut self, node_id: u32, options: &GraphVizSnapshotOptions) {
+        self.recent_nodes.push_back(node_id);
+        while self.recent_nodes.len() > options.highlighted_nodes {
+            self.recent_nodes.pop_front();
+        }
+    }
+
+    /** Counts a new event and tells whether a frame is due. **/
+    pub fn on_event(&mut self, elapsed: Duration, options: &GraphVizSnapshotOptions) -> bool {
+        self.events_since_last_frame += 1;
+        options.every_events.is_some_and(|every| self.events_since_last_frame >= every.max(1))
+            || options.every_duration.is_some_and(|every| elapsed >= self.last_frame_elapsed + every)
+    }
+
+    /** Name of the new frame. **/
+    pub fn on_frame(&mut self, elapsed: Duration) -> String {
+        self.events_since_last_frame = 0;
+        self.last_frame_elapsed = elapsed;
+        let name = format!("frame_{:05}", self.frames.len());
+        self.frames.push(name.clone());
+        name
+    }
+}
+
+
+/**
+ * A self-contained HTML page stepping through rendered frames.
+ **/
+pub(crate) fn html_player(frame_files: &[String]) -> String {
+    let mut frames = String::new();
+    for (index, file) in frame_files.iter().enumerate() {
+        let separator = if index == 0 { "" } else { ", " };
+        let _ = write!(frames, "{}\"{}\"", separator, file.replace('\\', "\\\\").replace('"', "\\\""));
+    }
+    format!(r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Exploration</title>
+<style>
+body {{ font-family: sans-serif; text-align: center; }}
+img {{ max-width: 100%; border: 1px solid #ccc; }}
+</style>
+</head>
+<body>
+<div>
+<button id="first">&#x23EE;</button>
+<button id="previous">&#x23F4;</button>
+<button id="play">&#x23EF;</button>
+<button id="next">&#x23F5;</button>
+<button id="last">&#x23ED;</button>
+<input id="slider" type="range" min="0" value="0">
+<span id="counter"></span>
+</div>
+<img id="frame" alt="frame">
+<script>
+const frames = [{frames}];
+let current = 0;
+let timer = null;
+const slider = document.getElementById("slider");
+slider.max = Math.max(frames.length - 1, 0);
+function show(index) {{
+    current = Math.min(Math.max(index, 0), frames.length - 1);
+    document.getElementById("frame").src = frames[current];
+    document.getElementById("counter").textContent = (current + 1) + " / " + frames.length;
+    slider.value = current;
+}}
+function toggle() {{
+    if (timer) {{ clearInterval(timer); timer = null; return; }}
+    timer = setInterval(() => {{
+        if (current >= frames.length - 1) {{ clearInterval(timer); timer = null; }} else {{ show(current + 1); }}
+    }}, 500);
+}}
+document.getElementById("first").onclick = () => show(0);
+document.getElementById("previous").onclick = () => show(current - 1);
+document.getElementById("play").onclick = toggle;
+document.getElementById("next").onclick = () => show(current + 1);
+document.getElementById("last").onclick = () => show(frames.length - 1);
+slider.oninput = () => show(Number(slider.value));
+if (frames.length > 0) {{ show(0); }}
+</script>
+</body>
+</html>
+"#)
+}
@@ -14,6 +14,14 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+use std::path::Path;
+use std::process::{Command, Output};
+
+use graphviz_dot_builder::edge::style::GraphvizEdgeStyleItem;
+use graphviz_dot_builder::item::item::GraphVizGraphItem;
+use graphviz_dot_builder::item::node::node::GraphVizNode;
+use graphviz_dot_builder::item::node::style::{GraphvizNodeStyleItem, GvNodeStyle};
+use graphviz_dot_builder::traits::GraphVizOutputFormat;
 
 
 pub fn get_anchor_id(prefix : &str, id: u32) -> String {
@@ -34,3 +42,82 @@ pub fn get_step_id(prefix : &str,
     format!("{:}_s_{:}_{:}", prefix, origin_id, target_id)
 }
 
+pub fn get_truncated_children_id(prefix : &str, parent_id: u32) -> String {
+    format!("{:}_t{:}", prefix, parent_id)
+}
+
+
+
+/** Adds attributes to the style of an edge, replacing those of the same kind. **/
+pub(crate) fn merge_edge_style(style: &mut Vec<GraphvizEdgeStyleItem>, added: &[GraphvizEdgeStyleItem]) {
+    style.retain(|item| !added.iter().any(|other| std::mem::discriminant(other) == std::mem::discriminant(item)));
+    style.extend(added.iter().cloned());
+}
+
+
+/**
+ * Adds attributes to the style of a node or cluster.
+ * The kinds of an added style (e.g. filled) are merged with those already there (e.g. dashed) instead of replacing them,
+ * the merged style coming last so that it is not overridden by an added fill colour, which is printed with style=filled.
+ **/
+pub(crate) fn merge_node_style(style: &mut Vec<GraphvizNodeStyleItem>, added: &[GraphvizNodeStyleItem]) {
+    let added_kinds: Option<GvNodeStyle> = added.iter().fold(None, |kinds, item| match item {
+        GraphvizNodeStyleItem::Style(item_kinds) => Some([kinds.unwrap_or_default(), item_kinds.clone()].concat()),
+        GraphvizNodeStyleItem::FillColor(_) => Some(kinds.unwrap_or_default()),
+        _ => kinds,
+    });
+    style.extend(added.iter().filter(|item| !matches!(item, GraphvizNodeStyleItem::Style(_))).cloned());
+    let Some(added_kinds) = added_kinds else { return };
+    let mut kinds: GvNodeStyle = vec![];
+    style.retain(|item| match item {
+        GraphvizNodeStyleItem::Style(item_kinds) => {
+            kinds.extend(item_kinds.iter().cloned());
+            false
+        }
+        _ => true,
+    });
+    for kind in added_kinds {
+        if !kinds.contains(&kind) {
+            kinds.push(kind);
+        }
+    }
+    if !kinds.is_empty() {
+        style.push(GraphvizNodeStyleItem::Style(kinds));
+    }
+}
+
+
+/**
+ * An item printed as the given DOT statement, for the attributes the builder does not have (e.g. xlabel or rank).
+ * A node without style is printed as its id followed by a semicolon.
+ **/
+pub(crate) fn raw_dot_statement(statement: String) -> GraphVizGraphItem {
+    GraphVizGraphItem::Node(GraphVizNode::new(statement, vec![]))
+}
+
+
+/** Escapes a text to be written between the double quotes of a DOT string, which the builder does not do. **/
+pub(crate) fn escape_dot_string(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+
+/**
+ * Renders an already written .dot file with the dot binary, next to it,
+ * as GraphVizDiGraph::print_dot does after writing it.
+ **/
+pub(crate) fn render_dot_file(
+    dot_file_path: &Path,
+    output_format: &GraphVizOutputFormat,
+) -> std::io::Result<Output> {
+    let command_format_argument = match output_format {
+        GraphVizOutputFormat::svg => "-Tsvg:cairo",
+        GraphVizOutputFormat::png => "-Tpng",
+    };
+    Command::new("dot")
+        .arg(command_format_argument)
+        .arg(dot_file_path)
+        .arg("-o")
+        .arg(dot_file_path.with_extension(output_format.to_string()))
+        .output()
+}
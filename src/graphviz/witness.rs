@@ -0,0 +1,66 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+
+/** The nodes and steps (origin, target) on the paths from the initial node to the witness targets. **/
+#[derive(Clone, PartialEq, Debug, Eq, Default)]
+pub(crate) struct WitnessPaths {
+    pub nodes: HashSet<u32>,
+    pub steps: HashSet<(u32, u32)>,
+}
+
+
+/**
+ * Finds, for each target, a shortest path from the initial node in the graph given by its steps (origin, target).
+ * When memoization makes a node reachable through several paths, the one discovered first among
+ * the shortest ones is kept. Targets that cannot be reached from the initial node are ignored.
+ **/
+pub(crate) fn find_witness_paths(
+    steps: &[(u32, u32)],
+    initial_node_id: u32,
+    targets: &[u32],
+) -> WitnessPaths {
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    for (origin, target) in steps {
+        children.entry(*origin).or_default().push(*target);
+    }
+    // breadth-first search from the initial node, keeping the first parent through which each node is reached
+    let mut parents: HashMap<u32, u32> = HashMap::new();
+    let mut reached: HashSet<u32> = HashSet::from([initial_node_id]);
+    let mut queue = VecDeque::from([initial_node_id]);
+    while let Some(node) = queue.pop_front() {
+        for child in children.get(&node).into_iter().flatten() {
+            if reached.insert(*child) {
+                parents.insert(*child, node);
+                queue.push_back(*child);
+            }
+        }
+    }
+
+    let mut paths = WitnessPaths::default();
+    for target in targets.iter().filter(|target| reached.contains(target)) {
+        let mut node = *target;
+        // paths share their prefixes, hence a node already on a path ends the walk
+        while paths.nodes.insert(node) {
+            let Some(parent) = parents.get(&node) else { break };
+            paths.steps.insert((*parent, node));
+            node = *parent;
+        }
+    }
+    paths
+}
@@ -15,10 +15,23 @@ limitations under the License.
 */
 
 
+pub mod control;
+pub(crate) mod dispatch;
+pub mod driver;
+pub mod error;
+pub mod layout;
 pub mod logger;
+pub mod logger_registry;
+pub mod meta;
+pub mod registry;
+pub mod report;
+pub mod threaded;
+pub(crate) mod util;
 
 pub mod stepstrace;
 pub mod nodesprint;
+pub mod record;
+pub mod statistics;
 
 
 #[cfg(feature = "graphviz")]
@@ -27,4 +40,17 @@ pub mod graphviz;
 #[cfg(feature = "nfait")]
 pub mod nfait;
 
+#[cfg(feature = "graphml")]
+pub mod graphml;
 
+#[cfg(feature = "gexf")]
+pub mod gexf;
+
+#[cfg(feature = "mermaid")]
+pub mod mermaid;
+
+#[cfg(feature = "aut")]
+pub mod aut;
+
+#[cfg(feature = "prism")]
+pub mod prism;
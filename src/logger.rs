@@ -14,24 +14,52 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use std::collections::HashMap;
-use std::sync::Arc;
-
 use graph_process_manager_core::process::config::AbstractProcessConfiguration;
-use graph_process_manager_core::process::event::ExplorationEvent;
 use graph_process_manager_core::process::manager::GenericProcessManager;
 
+use crate::control::{LoggerControl, TerminationReason};
+use crate::dispatch::borrow_loggers;
+use crate::driver::LoggerDriver;
+use crate::error::LoggerError;
+use crate::meta::EventMeta;
+use crate::registry::NodeRegistryPolicy;
+use crate::report::LoggersRunReport;
+
+
+/**
+ * Result of a logger callback.
+ * Ok carries a LoggerControl through which a logger may request the end of the exploration.
+ **/
+pub type LoggerResult = Result<LoggerControl, LoggerError>;
 
+
+/**
+ * A logger observing the exploration of a process.
+ *
+ * Every callback about an exploration event is given the EventMeta computed for that event.
+ **/
 pub trait AbstractProcessLogger<Conf: AbstractProcessConfiguration> {
 
-    fn log_initialize_process(&mut self, _manager: &GenericProcessManager<Conf>) {}
+    /**
+     * Name used to identify the logger in a LoggersRunReport.
+     **/
+    fn get_logger_name(&self) -> String {
+        std::any::type_name::<Self>().to_string()
+    }
+
+    fn log_initialize_process(&mut self, _manager: &GenericProcessManager<Conf>) -> LoggerResult {
+        Ok(LoggerControl::Continue)
+    }
 
     fn log_new_node(
         &mut self,
         _context_and_param: &Conf::ContextAndParameterization,
+        _meta: &EventMeta<Conf>,
         _new_node_id: u32,
         _new_node: &Conf::DomainSpecificNode,
-    ) {}
+    ) -> LoggerResult {
+        Ok(LoggerControl::Continue)
+    }
 
     /**
      * target_node is looked up from previously received NewNode events,
@@ -41,32 +69,132 @@ pub trait AbstractProcessLogger<Conf: AbstractProcessConfiguration> {
     fn log_new_step(
         &mut self,
         _context_and_param: &Conf::ContextAndParameterization,
+        _meta: &EventMeta<Conf>,
         _origin_node_id: u32,
         _step: &Conf::DomainSpecificStep,
         _target_node_id: u32,
         _target_node: &Conf::DomainSpecificNode,
-    ) {}
+    ) -> LoggerResult {
+        Ok(LoggerControl::Continue)
+    }
 
     fn log_all_children_processed(
         &mut self,
         _context_and_param: &Conf::ContextAndParameterization,
+        _meta: &EventMeta<Conf>,
         _parent_node_id: u32,
-    ) {}
+    ) -> LoggerResult {
+        Ok(LoggerControl::Continue)
+    }
 
     fn log_notify_node_without_children(
         &mut self,
         _context_and_param: &Conf::ContextAndParameterization,
+        _meta: &EventMeta<Conf>,
         _node_id: u32,
-    ) {}
+    ) -> LoggerResult {
+        Ok(LoggerControl::Continue)
+    }
 
     fn log_filtered(
         &mut self,
         _context_and_param: &Conf::ContextAndParameterization,
+        _meta: &EventMeta<Conf>,
         _parent_node_id: u32,
         _filtration_result: &Conf::FiltrationResult,
-    ) {}
+    ) -> LoggerResult {
+        Ok(LoggerControl::Continue)
+    }
 
-    fn log_terminate_process(&mut self, _manager: &GenericProcessManager<Conf>) {}
+    /**
+     * Called once at the end of the run, whether the exploration completed
+     * or was stopped early by a logger.
+     * The control returned here is ignored.
+     **/
+    fn log_terminate_process(
+        &mut self,
+        _manager: &GenericProcessManager<Conf>,
+        _reason: &TerminationReason,
+    ) -> LoggerResult {
+        Ok(LoggerControl::Continue)
+    }
+}
+
+
+/**
+ * A mutable reference to a logger is a logger,
+ * so that loggers owned elsewhere can be handed to the drivers.
+ **/
+impl<Conf, L> AbstractProcessLogger<Conf> for &mut L
+where
+    Conf: AbstractProcessConfiguration,
+    L: AbstractProcessLogger<Conf> + ?Sized,
+{
+    fn get_logger_name(&self) -> String {
+        (**self).get_logger_name()
+    }
+
+    fn log_initialize_process(&mut self, manager: &GenericProcessManager<Conf>) -> LoggerResult {
+        (**self).log_initialize_process(manager)
+    }
+
+    fn log_new_node(
+        &mut self,
+        context_and_param: &Conf::ContextAndParameterization,
+        meta: &EventMeta<Conf>,
+        new_node_id: u32,
+        new_node: &Conf::DomainSpecificNode,
+    ) -> LoggerResult {
+        (**self).log_new_node(context_and_param, meta, new_node_id, new_node)
+    }
+
+    fn log_new_step(
+        &mut self,
+        context_and_param: &Conf::ContextAndParameterization,
+        meta: &EventMeta<Conf>,
+        origin_node_id: u32,
+        step: &Conf::DomainSpecificStep,
+        target_node_id: u32,
+        target_node: &Conf::DomainSpecificNode,
+    ) -> LoggerResult {
+        (**self).log_new_step(context_and_param, meta, origin_node_id, step, target_node_id, target_node)
+    }
+
+    fn log_all_children_processed(
+        &mut self,
+        context_and_param: &Conf::ContextAndParameterization,
+        meta: &EventMeta<Conf>,
+        parent_node_id: u32,
+    ) -> LoggerResult {
+        (**self).log_all_children_processed(context_and_param, meta, parent_node_id)
+    }
+
+    fn log_notify_node_without_children(
+        &mut self,
+        context_and_param: &Conf::ContextAndParameterization,
+        meta: &EventMeta<Conf>,
+        node_id: u32,
+    ) -> LoggerResult {
+        (**self).log_notify_node_without_children(context_and_param, meta, node_id)
+    }
+
+    fn log_filtered(
+        &mut self,
+        context_and_param: &Conf::ContextAndParameterization,
+        meta: &EventMeta<Conf>,
+        parent_node_id: u32,
+        filtration_result: &Conf::FiltrationResult,
+    ) -> LoggerResult {
+        (**self).log_filtered(context_and_param, meta, parent_node_id, filtration_result)
+    }
+
+    fn log_terminate_process(
+        &mut self,
+        manager: &GenericProcessManager<Conf>,
+        reason: &TerminationReason,
+    ) -> LoggerResult {
+        (**self).log_terminate_process(manager, reason)
+    }
 }
 
 
@@ -76,56 +204,55 @@ pub trait AbstractProcessLogger<Conf: AbstractProcessConfiguration> {
  * Calls log_initialize on each logger, then iterates the manager and dispatches
  * each ExplorationEvent to the appropriate logger method, then calls
  * log_terminate_process when the iterator is exhausted.
+ * See LoggerDriver to interleave the exploration with other code.
  *
  * A local id→node registry is maintained so that log_new_step can always supply
  * target_node, even for back-edges to already-memoized nodes.
+ * Without memoization, nodes are dropped from it as soon as they are complete;
+ * see drive_loggers_with_options to bound it in memoized runs.
+ *
+ * Any logger may stop the exploration early by returning a LoggerControl other than Continue.
+ * log_terminate_process is then still called on every logger, with the reason for stopping.
+ *
+ * A logger whose callback returns an error is disabled for the rest of the run
+ * and the failure is recorded in the returned LoggersRunReport.
  **/
+#[must_use]
 pub fn drive_loggers<Conf: 'static + AbstractProcessConfiguration>(
     manager: &mut GenericProcessManager<Conf>,
-    loggers: &mut [Box<dyn AbstractProcessLogger<Conf>>],
-) {
-    for logger in loggers.iter_mut() {
-        logger.log_initialize_process(manager);
-    }
-
-    let mut node_registry: HashMap<u32, Arc<Conf::DomainSpecificNode>> = HashMap::new();
-
-    while let Some(event) = manager.next() {
-        let ctx = &manager.context_and_param;
-        match &event {
-            ExplorationEvent::NewNode { id, node } => {
-                node_registry.insert(*id, Arc::clone(node));
-                for logger in loggers.iter_mut() {
-                    logger.log_new_node(ctx, *id, node.as_ref());
-                }
-            }
-            ExplorationEvent::NewStep { origin_node_id, step, target_node_id } => {
-                if let Some(target_node) = node_registry.get(target_node_id) {
-                    let target = Arc::clone(target_node);
-                    for logger in loggers.iter_mut() {
-                        logger.log_new_step(ctx, *origin_node_id, step, *target_node_id, &target);
-                    }
-                }
-            }
-            ExplorationEvent::AllChildrenProcessed { parent_node_id } => {
-                for logger in loggers.iter_mut() {
-                    logger.log_all_children_processed(ctx, *parent_node_id);
-                }
-            }
-            ExplorationEvent::NodeWithoutChildren { node_id } => {
-                for logger in loggers.iter_mut() {
-                    logger.log_notify_node_without_children(ctx, *node_id);
-                }
-            }
-            ExplorationEvent::Filtered { parent_node_id, filtration_result } => {
-                for logger in loggers.iter_mut() {
-                    logger.log_filtered(ctx, *parent_node_id, filtration_result);
-                }
-            }
-        }
-    }
-
-    for logger in loggers.iter_mut() {
-        logger.log_terminate_process(manager);
+    loggers: &mut [Box<dyn AbstractProcessLogger<Conf> + '_>],
+) -> LoggersRunReport {
+    drive_loggers_with_options(manager, loggers, &DriveLoggersOptions::default())
+}
+
+
+#[derive(Clone, PartialEq, Debug, Eq, Hash, Default)]
+pub struct DriveLoggersOptions {
+    pub node_registry_policy: NodeRegistryPolicy,
+    /**
+     * Whether to catch panics in logger callbacks.
+     * A logger that panics is then disabled for the rest of the run, as if it had returned an error,
+     * and the panic is recorded in the panics of the LoggersRunReport.
+     * The panic hook (which by default prints the panic message) is still called.
+     **/
+    pub supervised: bool,
+}
+
+impl DriveLoggersOptions {
+    pub fn new(node_registry_policy: NodeRegistryPolicy) -> Self {
+        Self { node_registry_policy, supervised: false }
     }
 }
+
+
+/**
+ * Same as drive_loggers, with some options.
+ **/
+#[must_use]
+pub fn drive_loggers_with_options<Conf: 'static + AbstractProcessConfiguration>(
+    manager: &mut GenericProcessManager<Conf>,
+    loggers: &mut [Box<dyn AbstractProcessLogger<Conf> + '_>],
+    options: &DriveLoggersOptions,
+) -> LoggersRunReport {
+    LoggerDriver::with_options(manager, borrow_loggers(loggers), options).finish()
+}
@@ -0,0 +1,168 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::any::Any;
+use std::fmt;
+use std::marker::PhantomData;
+
+use graph_process_manager_core::process::config::AbstractProcessConfiguration;
+use graph_process_manager_core::process::manager::GenericProcessManager;
+
+use crate::driver::LoggerDriver;
+use crate::logger::{AbstractProcessLogger, DriveLoggersOptions};
+use crate::report::LoggersRunReport;
+
+
+/**
+ * A logger that builds something which is worth getting back once the run is finished.
+ **/
+pub trait LoggerWithProduct {
+    type Product;
+
+    fn into_product(self) -> Self::Product;
+}
+
+
+/**
+ * Typed reference to a logger added to a LoggerRegistry.
+ **/
+pub struct LoggerHandle<L> {
+    index: usize,
+    logger_type: PhantomData<fn() -> L>,
+}
+
+impl<L> LoggerHandle<L> {
+    /** Index of the logger in the LoggersRunReport of the registry. **/
+    pub fn get_index(&self) -> usize {
+        self.index
+    }
+}
+
+impl<L> Clone for LoggerHandle<L> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<L> Copy for LoggerHandle<L> {}
+
+impl<L> fmt::Debug for LoggerHandle<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LoggerHandle<{}>({})", std::any::type_name::<L>(), self.index)
+    }
+}
+
+
+trait AnyProcessLogger<Conf: AbstractProcessConfiguration>: AbstractProcessLogger<Conf> + Any {}
+
+impl<Conf: AbstractProcessConfiguration, L: AbstractProcessLogger<Conf> + Any> AnyProcessLogger<Conf> for L {}
+
+
+// stands for a logger that was taken back, so that the indices of the others do not change
+struct TakenLogger;
+
+impl<Conf: AbstractProcessConfiguration> AbstractProcessLogger<Conf> for TakenLogger {}
+
+
+/**
+ * Owns a set of loggers and gives typed access to them, in particular once a run is finished.
+ *
+ * Adding a logger returns a LoggerHandle, through which the concrete logger
+ * (or, for a LoggerWithProduct, what it has built) can be taken back.
+ **/
+pub struct LoggerRegistry<Conf: AbstractProcessConfiguration> {
+    loggers: Vec<Option<Box<dyn AnyProcessLogger<Conf>>>>,
+}
+
+impl<Conf: AbstractProcessConfiguration> Default for LoggerRegistry<Conf> {
+    fn default() -> Self {
+        Self { loggers: vec![] }
+    }
+}
+
+impl<Conf: 'static + AbstractProcessConfiguration> LoggerRegistry<Conf> {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add<L: AbstractProcessLogger<Conf> + 'static>(&mut self, logger: L) -> LoggerHandle<L> {
+        self.loggers.push(Some(Box::new(logger)));
+        LoggerHandle { index: self.loggers.len() - 1, logger_type: PhantomData }
+    }
+
+    /** Returns None if the logger has been taken back. **/
+    pub fn get<L: 'static>(&self, handle: LoggerHandle<L>) -> Option<&L> {
+        let logger: &dyn Any = self.loggers.get(handle.index)?.as_deref()?;
+        logger.downcast_ref::<L>()
+    }
+
+    /** Returns None if the logger has been taken back. **/
+    pub fn get_mut<L: 'static>(&mut self, handle: LoggerHandle<L>) -> Option<&mut L> {
+        let logger: &mut dyn Any = self.loggers.get_mut(handle.index)?.as_deref_mut()?;
+        logger.downcast_mut::<L>()
+    }
+
+    /**
+     * Removes the logger from the registry and gives it back.
+     * Returns None if it has already been taken back.
+     **/
+    pub fn take<L: 'static>(&mut self, handle: LoggerHandle<L>) -> Option<L> {
+        let logger: Box<dyn Any> = self.loggers.get_mut(handle.index)?.take()?;
+        logger.downcast::<L>().ok().map(|logger| *logger)
+    }
+
+    /** Same as take, but gives back what the logger has built. **/
+    pub fn take_product<L: LoggerWithProduct + 'static>(&mut self, handle: LoggerHandle<L>) -> Option<L::Product> {
+        self.take(handle).map(LoggerWithProduct::into_product)
+    }
+
+    /**
+     * A step-by-step driver over the loggers of the registry.
+     * Loggers that have been taken back are skipped.
+     **/
+    pub fn driver<'a>(
+        &'a mut self,
+        manager: &'a mut GenericProcessManager<Conf>,
+        options: &DriveLoggersOptions,
+    ) -> LoggerDriver<'a, Conf> {
+        let loggers: Vec<Box<dyn AbstractProcessLogger<Conf> + 'a>> = self
+            .loggers
+            .iter_mut()
+            .map(|slot| match slot.as_deref_mut() {
+                Some(logger) => Box::new(logger) as Box<dyn AbstractProcessLogger<Conf> + 'a>,
+                None => Box::new(TakenLogger),
+            })
+            .collect();
+        LoggerDriver::with_options(manager, loggers, options)
+    }
+
+    /** Same as drive_loggers, for the loggers of the registry. **/
+    #[must_use]
+    pub fn drive(&mut self, manager: &mut GenericProcessManager<Conf>) -> LoggersRunReport {
+        self.drive_with_options(manager, &DriveLoggersOptions::default())
+    }
+
+    /** Same as drive_loggers_with_options, for the loggers of the registry. **/
+    #[must_use]
+    pub fn drive_with_options(
+        &mut self,
+        manager: &mut GenericProcessManager<Conf>,
+        options: &DriveLoggersOptions,
+    ) -> LoggersRunReport {
+        self.driver(manager, options).finish()
+    }
+}
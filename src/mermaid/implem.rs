@@ -0,0 +1,108 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::fs;
+
+use graph_process_manager_core::process::config::AbstractProcessConfiguration;
+use graph_process_manager_core::process::manager::GenericProcessManager;
+
+use crate::control::{LoggerControl, TerminationReason};
+use crate::error::LoggerError;
+use crate::logger::{AbstractProcessLogger, LoggerResult};
+use crate::mermaid::logger::{mermaid_escape, GenericMermaidLogger};
+use crate::meta::EventMeta;
+use crate::util::create_folder;
+
+
+impl<Conf: AbstractProcessConfiguration + 'static> AbstractProcessLogger<Conf>
+    for GenericMermaidLogger<Conf>
+{
+    fn log_new_node(
+        &mut self,
+        ctx: &Conf::ContextAndParameterization,
+        _meta: &EventMeta<Conf>,
+        new_node_id: u32,
+        new_node: &Conf::DomainSpecificNode,
+    ) -> LoggerResult {
+        if self.node_limit.is_some_and(|limit| self.drawn_nodes.len() >= limit) {
+            self.hidden_nodes += 1;
+            return Ok(LoggerControl::Continue);
+        }
+        self.drawn_nodes.insert(new_node_id);
+        let node = format!(
+            "n{}[\"{}\"]",
+            new_node_id, mermaid_escape(&self.labeller.node_label(ctx, new_node))
+        );
+        match self.labeller.node_phase(ctx, new_node) {
+            Some(phase_id) => self.phases.entry(phase_id).or_default().push(node),
+            None => self.ungrouped_nodes.push(node),
+        }
+        Ok(LoggerControl::Continue)
+    }
+
+    fn log_new_step(
+        &mut self,
+        ctx: &Conf::ContextAndParameterization,
+        _meta: &EventMeta<Conf>,
+        origin_node_id: u32,
+        step: &Conf::DomainSpecificStep,
+        target_node_id: u32,
+        _target_node: &Conf::DomainSpecificNode,
+    ) -> LoggerResult {
+        if !self.drawn_nodes.contains(&origin_node_id) || !self.drawn_nodes.contains(&target_node_id) {
+            self.hidden_steps += 1;
+            return Ok(LoggerControl::Continue);
+        }
+        self.edges.push(format!(
+            "n{} -->|\"{}\"| n{}",
+            origin_node_id, mermaid_escape(&self.labeller.step_label(ctx, step)), target_node_id
+        ));
+        Ok(LoggerControl::Continue)
+    }
+
+    fn log_filtered(
+        &mut self,
+        ctx: &Conf::ContextAndParameterization,
+        _meta: &EventMeta<Conf>,
+        parent_node_id: u32,
+        filtration_result: &Conf::FiltrationResult,
+    ) -> LoggerResult {
+        if !self.drawn_nodes.contains(&parent_node_id) {
+            self.hidden_filtrations += 1;
+            return Ok(LoggerControl::Continue);
+        }
+        self.filtration_counter += 1;
+        // filtration results are not part of any phase
+        self.ungrouped_nodes.push(format!(
+            "f{}[\"{}\"]:::filtration",
+            self.filtration_counter, mermaid_escape(&self.labeller.filtration_label(ctx, filtration_result))
+        ));
+        self.edges.push(format!("n{} -.-> f{}", parent_node_id, self.filtration_counter));
+        Ok(LoggerControl::Continue)
+    }
+
+    fn log_terminate_process(
+        &mut self,
+        _manager: &GenericProcessManager<Conf>,
+        _reason: &TerminationReason,
+    ) -> LoggerResult {
+        create_folder(&self.parent_folder)?;
+        let path = self.get_output_path();
+        fs::write(&path, self.to_mermaid())
+            .map_err(|e| LoggerError::io(format!("writing '{}'", path.display()), e))?;
+        Ok(LoggerControl::Continue)
+    }
+}
@@ -0,0 +1,56 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use graph_process_manager_core::process::config::AbstractProcessConfiguration;
+
+
+/**
+ * Gives the text labels of the nodes, steps and filtration results of a process
+ * in a Mermaid flowchart.
+ * Labels are plain text: quotes and line breaks are escaped by the logger.
+ **/
+pub trait MermaidProcessLabeller<Conf: AbstractProcessConfiguration> {
+
+    fn node_label(
+        &self,
+        ctx: &Conf::ContextAndParameterization,
+        node: &Conf::DomainSpecificNode,
+    ) -> String;
+
+    fn step_label(
+        &self,
+        ctx: &Conf::ContextAndParameterization,
+        step: &Conf::DomainSpecificStep,
+    ) -> String;
+
+    fn filtration_label(
+        &self,
+        ctx: &Conf::ContextAndParameterization,
+        filtration_result: &Conf::FiltrationResult,
+    ) -> String;
+
+    /// Assign a phase id to a node so it is grouped into a subgraph,
+    /// as with `GraphVizProcessDrawer::node_phase`.
+    /// Return `None` (the default) to leave the node ungrouped.
+    fn node_phase(
+        &self,
+        _ctx: &Conf::ContextAndParameterization,
+        _node: &Conf::DomainSpecificNode,
+    ) -> Option<usize> { None }
+
+    /// Title of the subgraph that groups all nodes of `phase_id`.
+    fn phase_title(&self, phase_id: usize) -> String { format!("phase {}", phase_id) }
+}
@@ -0,0 +1,145 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use graph_process_manager_core::process::config::AbstractProcessConfiguration;
+
+use crate::logger_registry::LoggerWithProduct;
+use crate::mermaid::format::MermaidProcessLoggerLayout;
+use crate::mermaid::labeller::MermaidProcessLabeller;
+
+
+/**
+ * Logger that writes the explored graph as a Mermaid flowchart "{output_file_name}.mmd"
+ * when the process terminates, e.g. to embed it in Markdown.
+ *
+ * Process nodes have ids "n{node_id}" and filtration results "f{counter}",
+ * the latter being of class "filtration".
+ *
+ * Beyond node_limit process nodes, further nodes are not drawn, nor are the steps and
+ * filtrations that involve them. They are counted instead in a final summary node of class "truncated".
+ **/
+pub struct GenericMermaidLogger<Conf : AbstractProcessConfiguration> {
+    pub labeller : Box<dyn MermaidProcessLabeller<Conf>>,
+    pub layout : MermaidProcessLoggerLayout,
+    pub node_limit : Option<usize>,
+    // ***
+    pub(crate) parent_folder : String,
+    pub(crate) output_file_name : String,
+    // ***
+    pub(crate) ungrouped_nodes : Vec<String>,
+    pub(crate) phases : BTreeMap<usize, Vec<String>>,
+    pub(crate) edges : Vec<String>,
+    pub(crate) drawn_nodes : HashSet<u32>,
+    pub(crate) filtration_counter : u32,
+    // elements that are not drawn because of node_limit
+    pub(crate) hidden_nodes : u32,
+    pub(crate) hidden_steps : u32,
+    pub(crate) hidden_filtrations : u32
+}
+
+impl<Conf : AbstractProcessConfiguration> GenericMermaidLogger<Conf> {
+
+    pub fn new(labeller : Box<dyn MermaidProcessLabeller<Conf>>,
+               layout : MermaidProcessLoggerLayout,
+               node_limit : Option<usize>,
+               parent_folder : String,
+               output_file_name : String) -> Self {
+        Self {
+            labeller,
+            layout,
+            node_limit,
+            parent_folder,
+            output_file_name,
+            ungrouped_nodes : vec![],
+            phases : BTreeMap::new(),
+            edges : vec![],
+            drawn_nodes : HashSet::new(),
+            filtration_counter : 0,
+            hidden_nodes : 0,
+            hidden_steps : 0,
+            hidden_filtrations : 0
+        }
+    }
+
+    pub fn get_output_path(&self) -> PathBuf {
+        [&self.parent_folder, &format!("{}.mmd", self.output_file_name)].iter().collect()
+    }
+
+    /** The Mermaid document for the events logged so far. **/
+    pub fn to_mermaid(&self) -> String {
+        let direction = match self.layout {
+            MermaidProcessLoggerLayout::Horizontal => "LR",
+            MermaidProcessLoggerLayout::Vertical   => "TD",
+        };
+        let mut mermaid = format!("flowchart {}\n", direction);
+        mermaid.push_str("    classDef filtration fill:#fdd,stroke:#c33,stroke-dasharray:4\n");
+        mermaid.push_str("    classDef truncated fill:#eee,stroke:#999,font-style:italic\n");
+        for node in &self.ungrouped_nodes {
+            let _ = writeln!(mermaid, "    {}", node);
+        }
+        for (phase_id, nodes) in &self.phases {
+            let _ = writeln!(
+                mermaid,
+                "    subgraph phase{} [\"{}\"]",
+                phase_id, mermaid_escape(&self.labeller.phase_title(*phase_id))
+            );
+            for node in nodes {
+                let _ = writeln!(mermaid, "        {}", node);
+            }
+            mermaid.push_str("    end\n");
+        }
+        for edge in &self.edges {
+            let _ = writeln!(mermaid, "    {}", edge);
+        }
+        if self.hidden_nodes > 0 {
+            let _ = writeln!(
+                mermaid,
+                "    truncated[\"truncated after {} nodes : {} more nodes, {} more steps and {} more filtrations\"]:::truncated",
+                self.drawn_nodes.len(), self.hidden_nodes, self.hidden_steps, self.hidden_filtrations
+            );
+        }
+        mermaid
+    }
+}
+
+
+/**
+ * Escapes a label so that it can be put between double quotes in a Mermaid document.
+ * Entity codes start with a #, hence literal ones are escaped first.
+ **/
+pub(crate) fn mermaid_escape(label: &str) -> String {
+    label
+        .replace('#', "#35;")
+        .replace('"', "#quot;")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+
+/**
+ * The product is the Mermaid document.
+ **/
+impl<Conf : AbstractProcessConfiguration> LoggerWithProduct for GenericMermaidLogger<Conf> {
+    type Product = String;
+
+    fn into_product(self) -> String {
+        self.to_mermaid()
+    }
+}
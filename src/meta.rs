@@ -0,0 +1,83 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::time::{Duration, Instant};
+
+use graph_process_manager_core::process::config::AbstractProcessConfiguration;
+
+
+/**
+ * Information computed once by the drivers for each exploration event
+ * and given to every logger callback along with that event.
+ *
+ * The subject node of an event is the new node for NewNode, the target node for NewStep
+ * and the node the event is about for the other events.
+ * depth, parent_node_id and discovery_index describe the subject node;
+ * they are None if it has already left the node registry (see NodeRegistryPolicy).
+ **/
+pub struct EventMeta<'a, Conf: AbstractProcessConfiguration> {
+    /** Rank of the event among the events emitted by the process manager, starting at 0. **/
+    pub event_index: u64,
+    /**
+     * Time elapsed between the initialization of the loggers and the emission of the event.
+     * When replaying a recording, that of the replay.
+     **/
+    pub elapsed: Duration,
+    /**
+     * Number of steps between the initial node and the subject node,
+     * along the path through which the subject node was discovered.
+     **/
+    pub depth: Option<u32>,
+    /** Node from which the subject node was discovered, None for the initial node. **/
+    pub parent_node_id: Option<u32>,
+    /** Rank of the subject node among discovered nodes, starting at 0 for the initial node. **/
+    pub discovery_index: Option<u32>,
+    /** For NewStep, the origin node of the step. **/
+    pub origin_node: Option<&'a Conf::DomainSpecificNode>,
+}
+
+
+/** Index and time of emission of an exploration event. **/
+#[derive(Clone, Copy, PartialEq, Debug, Eq)]
+pub(crate) struct EventStamp {
+    pub index: u64,
+    pub elapsed: Duration,
+}
+
+
+/** Stamps exploration events as they are emitted. **/
+pub(crate) struct EventClock {
+    start: Instant,
+    next_index: u64,
+}
+
+impl EventClock {
+
+    pub fn new() -> Self {
+        Self { start: Instant::now(), next_index: 0 }
+    }
+
+    pub fn tick(&mut self) -> EventStamp {
+        let stamp = EventStamp { index: self.next_index, elapsed: self.start.elapsed() };
+        self.next_index += 1;
+        stamp
+    }
+
+    /** Number of events stamped so far. **/
+    pub fn count(&self) -> u64 {
+        self.next_index
+    }
+}
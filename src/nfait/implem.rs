@@ -14,7 +14,7 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
 use autour_core::traits::letter::AutLetter;
 use autour_core::traits::repr::AutGraphvizDrawable;
@@ -23,8 +23,12 @@ use graph_process_manager_core::process::config::AbstractProcessConfiguration;
 use graph_process_manager_core::process::manager::GenericProcessManager;
 use graphviz_dot_builder::traits::DotPrintable;
 
-use crate::logger::AbstractProcessLogger;
+use crate::control::{LoggerControl, TerminationReason};
+use crate::error::LoggerError;
+use crate::logger::{AbstractProcessLogger, LoggerResult};
+use crate::meta::EventMeta;
 use crate::nfait::logger::{GenericNFAITLogger, NFAITBuilderPrinter};
+use crate::util::check_dot_output;
 
 
 impl<Conf, Letter, BP> AbstractProcessLogger<Conf> for GenericNFAITLogger<Conf, Letter, BP>
@@ -36,48 +40,63 @@ where
     fn log_new_node(
         &mut self,
         context_and_param: &Conf::ContextAndParameterization,
+        _meta: &EventMeta<Conf>,
         new_node_id: u32,
         new_node: &Conf::DomainSpecificNode,
-    ) {
+    ) -> LoggerResult {
         let nfa_state_id = self.next_nfa_state_id;
         self.next_nfa_state_id += 1;
         self.explo_node_id_to_nfa_state_id_map.insert(new_node_id, nfa_state_id);
         if self.builder_printer.is_node_final(context_and_param, new_node) {
             self.finals.insert(nfa_state_id);
         }
+        Ok(LoggerControl::Continue)
     }
 
     fn log_new_step(
         &mut self,
         context_and_param: &Conf::ContextAndParameterization,
+        _meta: &EventMeta<Conf>,
         origin_node_id: u32,
         step: &Conf::DomainSpecificStep,
         target_node_id: u32,
         _target_node: &Conf::DomainSpecificNode,
-    ) {
-        let nfa_orig = *self.explo_node_id_to_nfa_state_id_map.get(&origin_node_id).unwrap();
-        let nfa_targ = *self.explo_node_id_to_nfa_state_id_map.get(&target_node_id).unwrap();
+    ) -> LoggerResult {
+        let nfa_state_id = |node_id: u32| {
+            self.explo_node_id_to_nfa_state_id_map.get(&node_id).copied().ok_or_else(|| {
+                LoggerError::Custom(format!("no NFA state for the node {} of a step", node_id))
+            })
+        };
+        let nfa_orig = nfa_state_id(origin_node_id)?;
+        let nfa_targ = nfa_state_id(target_node_id)?;
         match self.builder_printer.step_into_letter(context_and_param, step) {
             None => {
-                self.epsilon_trans.entry(nfa_orig).or_insert_with(HashSet::new).insert(nfa_targ);
+                self.epsilon_trans.entry(nfa_orig).or_default().insert(nfa_targ);
             }
             Some(letter) => {
                 self.alphabet.insert(letter);
                 self.transitions
                     .entry(nfa_orig)
-                    .or_insert_with(HashMap::new)
+                    .or_default()
                     .entry(letter)
-                    .or_insert_with(HashSet::new)
+                    .or_default()
                     .insert(nfa_targ);
             }
         }
+        Ok(LoggerControl::Continue)
     }
 
-    fn log_terminate_process(&mut self, _manager: &GenericProcessManager<Conf>) {
-        let got_nfait = self.get_nfait();
+    fn log_terminate_process(
+        &mut self,
+        _manager: &GenericProcessManager<Conf>,
+        _reason: &TerminationReason,
+    ) -> LoggerResult {
         if let Some((access, format)) = &self.draw {
+            let got_nfait = self.get_nfait();
             let graph = got_nfait.to_dot(*access, &HashSet::new(), &self.builder_printer);
-            let _ = graph.print_dot(&[self.parent_folder.clone()], &self.name, format);
+            let output = graph.print_dot(std::slice::from_ref(&self.parent_folder), &self.name, format);
+            check_dot_output(&self.name, output)?;
         }
+        Ok(LoggerControl::Continue)
     }
 }
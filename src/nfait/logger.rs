@@ -23,6 +23,7 @@ use autour_core::traits::repr::AbstractLanguagePrinter;
 use graph_process_manager_core::process::config::AbstractProcessConfiguration;
 use graphviz_dot_builder::traits::GraphVizOutputFormat;
 
+use crate::logger_registry::LoggerWithProduct;
 use crate::nfait::builder::NFAITProcessBuilder;
 
 
@@ -110,10 +111,14 @@ impl<Conf, Letter,BP> GenericNFAITLogger<Conf, Letter,BP> where
 }
 
 
+impl<Conf, Letter, BP> LoggerWithProduct for GenericNFAITLogger<Conf, Letter, BP> where
+    Conf: AbstractProcessConfiguration,
+    Letter: AutLetter,
+    BP : NFAITBuilderPrinter<Conf, Letter> {
 
+    type Product = AutNFAIT<Letter>;
 
-
-
-
-
-
+    fn into_product(self) -> AutNFAIT<Letter> {
+        self.get_nfait()
+    }
+}
@@ -14,33 +14,37 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use std::fs;
 use std::path::PathBuf;
 
 use graph_process_manager_core::process::config::AbstractProcessConfiguration;
 use graph_process_manager_core::process::manager::GenericProcessManager;
 
-use crate::logger::AbstractProcessLogger;
+use crate::control::LoggerControl;
+use crate::logger::{AbstractProcessLogger, LoggerResult};
+use crate::meta::EventMeta;
 use crate::nodesprint::logger::GenericNodesPrintLogger;
+use crate::util::reset_folder;
 
 
 impl<Conf: AbstractProcessConfiguration + 'static> AbstractProcessLogger<Conf> for GenericNodesPrintLogger<Conf> {
 
-    fn log_initialize_process(&mut self, _manager: &GenericProcessManager<Conf>) {
-        let _ = fs::remove_dir_all(&self.parent_folder);
-        let _ = fs::create_dir_all(&self.parent_folder);
+    fn log_initialize_process(&mut self, _manager: &GenericProcessManager<Conf>) -> LoggerResult {
+        reset_folder(&self.parent_folder)?;
+        Ok(LoggerControl::Continue)
     }
 
     fn log_new_node(
         &mut self,
         context_and_param: &Conf::ContextAndParameterization,
+        _meta: &EventMeta<Conf>,
         new_node_id: u32,
         new_node: &Conf::DomainSpecificNode,
-    ) {
+    ) -> LoggerResult {
         if self.printer.should_print_node(context_and_param, new_node) {
             let file_name = format!("{}_node{}.{}", self.prefix, new_node_id, self.file_extension);
             let path_buf: PathBuf = [&self.parent_folder, &file_name].iter().collect();
-            self.printer.print_node(context_and_param, new_node, path_buf.as_path());
+            self.printer.print_node(context_and_param, new_node, path_buf.as_path())?;
         }
+        Ok(LoggerControl::Continue)
     }
 }
@@ -19,6 +19,8 @@ limitations under the License.
 use std::path::Path;
 use graph_process_manager_core::process::config::AbstractProcessConfiguration;
 
+use crate::error::LoggerError;
+
 
 pub trait NodesPrintProcessPrinter<Conf : AbstractProcessConfiguration> {
 
@@ -28,11 +30,15 @@ pub trait NodesPrintProcessPrinter<Conf : AbstractProcessConfiguration> {
         node: &Conf::DomainSpecificNode
     ) -> bool;
 
+    /**
+     * Prints the node into the file at path.
+     * An error is reported by the logger, which then receives no more events.
+     **/
     fn print_node(
         &self,
         context_and_param: &Conf::ContextAndParameterization,
         node: &Conf::DomainSpecificNode,
         path : &Path
-    );
+    ) -> Result<(), LoggerError>;
 
 }
\ No newline at end of file
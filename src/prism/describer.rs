@@ -0,0 +1,67 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use graph_process_manager_core::process::config::AbstractProcessConfiguration;
+
+
+/**
+ * Describes the states, transition probabilities and labels of the DTMC
+ * written by the GenericPrismLogger.
+ **/
+pub trait PrismProcessDescriber<Conf : AbstractProcessConfiguration> {
+
+    /// Names of the variables of the .sta file.
+    fn state_variables(&self) -> Vec<String> {
+        vec!["node".to_string()]
+    }
+
+    /// Values of the state_variables for a node.
+    /// Return `None` (the default) to use the id of the state of the node as its only value.
+    fn state_valuation(
+        &self,
+        _context_and_param: &Conf::ContextAndParameterization,
+        _node : &Conf::DomainSpecificNode
+    ) -> Option<Vec<i64>> {
+        None
+    }
+
+    /// Weight of a step. The probability of a transition is its weight divided by
+    /// the total weight of the steps from the same node, so the default of 1
+    /// gives a uniform distribution over the outgoing steps of each node.
+    fn step_weight(
+        &self,
+        _context_and_param: &Conf::ContextAndParameterization,
+        _step : &Conf::DomainSpecificStep
+    ) -> f64 {
+        1.0
+    }
+
+    /// Names of the labels of the .lab file besides the built-in "init" and "deadlock".
+    fn labels(&self) -> Vec<String> {
+        vec![]
+    }
+
+    /// Whether a node satisfies one of the labels.
+    fn has_label(
+        &self,
+        _context_and_param: &Conf::ContextAndParameterization,
+        _node : &Conf::DomainSpecificNode,
+        _label : &str
+    ) -> bool {
+        false
+    }
+
+}
@@ -0,0 +1,145 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::fs;
+
+use graph_process_manager_core::process::config::AbstractProcessConfiguration;
+use graph_process_manager_core::process::manager::GenericProcessManager;
+
+use crate::control::{LoggerControl, TerminationReason};
+use crate::error::LoggerError;
+use crate::logger::{AbstractProcessLogger, LoggerResult};
+use crate::meta::EventMeta;
+use crate::prism::logger::{GenericPrismLogger, DEADLOCK_LABEL, INIT_LABEL};
+use crate::util::create_folder;
+
+
+/** Whether a name is a PRISM identifier, i.e. a letter or underscore followed by letters, digits or underscores. **/
+fn is_prism_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+
+impl<Conf: AbstractProcessConfiguration> GenericPrismLogger<Conf> {
+
+    fn get_prism_state_id(&self, node_id: u32) -> Result<usize, LoggerError> {
+        self.explo_node_id_to_prism_state_id_map
+            .get(&node_id)
+            .copied()
+            .ok_or_else(|| LoggerError::Custom(format!("no DTMC state for node {}", node_id)))
+    }
+}
+
+
+impl<Conf: AbstractProcessConfiguration + 'static> AbstractProcessLogger<Conf> for GenericPrismLogger<Conf> {
+
+    fn log_initialize_process(&mut self, _manager: &GenericProcessManager<Conf>) -> LoggerResult {
+        for (kind, names) in [("state variable", &self.state_variables), ("label", &self.label_names)] {
+            if let Some(name) = names.iter().find(|name| !is_prism_identifier(name)) {
+                return Err(LoggerError::Custom(format!("{} \"{}\" is not a PRISM identifier", kind, name)));
+            }
+        }
+        Ok(LoggerControl::Continue)
+    }
+
+    fn log_new_node(
+        &mut self,
+        context_and_param: &Conf::ContextAndParameterization,
+        _meta: &EventMeta<Conf>,
+        new_node_id: u32,
+        new_node: &Conf::DomainSpecificNode,
+    ) -> LoggerResult {
+        let state_id = self.valuations.len();
+        let valuation = self.describer
+            .state_valuation(context_and_param, new_node)
+            .unwrap_or_else(|| vec![state_id as i64]);
+        if valuation.len() != self.state_variables.len() {
+            return Err(LoggerError::Custom(format!(
+                "node {} has {} values for the {} state variables",
+                new_node_id, valuation.len(), self.state_variables.len()
+            )));
+        }
+        self.explo_node_id_to_prism_state_id_map.insert(new_node_id, state_id);
+        self.valuations.push(valuation);
+
+        let mut label_indices = vec![];
+        if state_id == 0 {
+            label_indices.push(INIT_LABEL);
+        }
+        for (index, label) in self.label_names.iter().enumerate().skip(DEADLOCK_LABEL + 1) {
+            if self.describer.has_label(context_and_param, new_node, label) {
+                label_indices.push(index);
+            }
+        }
+        if !label_indices.is_empty() {
+            self.state_labels.insert(state_id, label_indices);
+        }
+        Ok(LoggerControl::Continue)
+    }
+
+    fn log_new_step(
+        &mut self,
+        context_and_param: &Conf::ContextAndParameterization,
+        _meta: &EventMeta<Conf>,
+        origin_node_id: u32,
+        step: &Conf::DomainSpecificStep,
+        target_node_id: u32,
+        _target_node: &Conf::DomainSpecificNode,
+    ) -> LoggerResult {
+        let prism_orig = self.get_prism_state_id(origin_node_id)?;
+        let prism_targ = self.get_prism_state_id(target_node_id)?;
+        let weight = self.describer.step_weight(context_and_param, step);
+        if !(weight.is_finite() && weight > 0.0) {
+            return Err(LoggerError::Custom(format!(
+                "step from node {} to node {} has weight {}, which is not strictly positive",
+                origin_node_id, target_node_id, weight
+            )));
+        }
+        *self.weights.entry(prism_orig).or_default().entry(prism_targ).or_default() += weight;
+        Ok(LoggerControl::Continue)
+    }
+
+    fn log_notify_node_without_children(
+        &mut self,
+        _context_and_param: &Conf::ContextAndParameterization,
+        _meta: &EventMeta<Conf>,
+        node_id: u32,
+    ) -> LoggerResult {
+        let state_id = self.get_prism_state_id(node_id)?;
+        let label_indices = self.state_labels.entry(state_id).or_default();
+        // labels are listed in increasing order, deadlock coming right after init
+        let position = usize::from(label_indices.first() == Some(&INIT_LABEL));
+        label_indices.insert(position, DEADLOCK_LABEL);
+        Ok(LoggerControl::Continue)
+    }
+
+    fn log_terminate_process(
+        &mut self,
+        _manager: &GenericProcessManager<Conf>,
+        _reason: &TerminationReason,
+    ) -> LoggerResult {
+        create_folder(&self.parent_folder)?;
+        let model = self.to_model();
+        for (extension, content) in [("sta", &model.states), ("tra", &model.transitions), ("lab", &model.labels)] {
+            let path = self.get_output_path(extension);
+            fs::write(&path, content)
+                .map_err(|e| LoggerError::io(format!("writing '{}'", path.display()), e))?;
+        }
+        Ok(LoggerControl::Continue)
+    }
+}
@@ -0,0 +1,128 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use graph_process_manager_core::process::config::AbstractProcessConfiguration;
+
+use crate::logger_registry::LoggerWithProduct;
+use crate::prism::describer::PrismProcessDescriber;
+use crate::prism::model::PrismExplicitModel;
+
+
+pub(crate) const INIT_LABEL : usize = 0;
+pub(crate) const DEADLOCK_LABEL : usize = 1;
+
+
+/**
+ * Logger that writes the explored graph as a DTMC in the PRISM explicit format
+ * ("{name}.sta", "{name}.tra" and "{name}.lab", also read by Storm) when the process terminates.
+ *
+ * States are numbered in order of discovery. The initial node is labelled "init"
+ * and the nodes notified as having no children are labelled "deadlock"
+ * (they have no outgoing transitions, which PRISM and Storm can fix with self-loops).
+ * Several steps between the same nodes make a single transition.
+ *
+ * The state variables and labels must be PRISM identifiers, and every valuation must have
+ * a value per state variable, otherwise the logger fails with a LoggerError::Custom.
+ **/
+pub struct GenericPrismLogger<Conf : AbstractProcessConfiguration> {
+    pub describer : Box<dyn PrismProcessDescriber<Conf>>,
+    // ***
+    pub(crate) parent_folder : String,
+    pub(crate) name : String,
+    // ***
+    pub(crate) state_variables : Vec<String>,
+    // "init", "deadlock", then the labels of the describer
+    pub(crate) label_names : Vec<String>,
+    pub(crate) explo_node_id_to_prism_state_id_map : BTreeMap<u32,usize>,
+    // valuation of each state, indexed by state id
+    pub(crate) valuations : Vec<Vec<i64>>,
+    // state id → indices of the labels it satisfies
+    pub(crate) state_labels : BTreeMap<usize,Vec<usize>>,
+    // origin state id → target state id → total weight of the steps
+    pub(crate) weights : BTreeMap<usize,BTreeMap<usize,f64>>
+}
+
+impl<Conf : AbstractProcessConfiguration> GenericPrismLogger<Conf> {
+
+    pub fn new(describer : Box<dyn PrismProcessDescriber<Conf>>,
+               parent_folder : String,
+               name : String) -> Self {
+        let mut label_names = vec!["init".to_string(), "deadlock".to_string()];
+        label_names.extend(describer.labels());
+        Self {
+            state_variables : describer.state_variables(),
+            describer,
+            parent_folder,
+            name,
+            label_names,
+            explo_node_id_to_prism_state_id_map : BTreeMap::new(),
+            valuations : vec![],
+            state_labels : BTreeMap::new(),
+            weights : BTreeMap::new()
+        }
+    }
+
+    pub fn get_output_path(&self, extension : &str) -> PathBuf {
+        [&self.parent_folder, &format!("{}.{}", self.name, extension)].iter().collect()
+    }
+
+    /** The model for the events logged so far. **/
+    pub fn to_model(&self) -> PrismExplicitModel {
+        let mut states = format!("({})\n", self.state_variables.join(","));
+        for (state_id, valuation) in self.valuations.iter().enumerate() {
+            let values : Vec<String> = valuation.iter().map(|value| value.to_string()).collect();
+            let _ = writeln!(states, "{}:({})", state_id, values.join(","));
+        }
+
+        let transitions_count : usize = self.weights.values().map(|targets| targets.len()).sum();
+        let mut transitions = format!("{} {}\n", self.valuations.len(), transitions_count);
+        for (origin, targets) in &self.weights {
+            let total : f64 = targets.values().sum();
+            for (target, weight) in targets {
+                let _ = writeln!(transitions, "{} {} {}", origin, target, weight / total);
+            }
+        }
+
+        let declarations : Vec<String> = self.label_names
+            .iter()
+            .enumerate()
+            .map(|(index, label)| format!("{}=\"{}\"", index, label))
+            .collect();
+        let mut labels = format!("{}\n", declarations.join(" "));
+        for (state_id, label_indices) in &self.state_labels {
+            let indices : Vec<String> = label_indices.iter().map(|index| index.to_string()).collect();
+            let _ = writeln!(labels, "{}: {}", state_id, indices.join(" "));
+        }
+
+        PrismExplicitModel { states, transitions, labels }
+    }
+}
+
+
+/**
+ * The product is the model, as written in the files.
+ **/
+impl<Conf : AbstractProcessConfiguration> LoggerWithProduct for GenericPrismLogger<Conf> {
+    type Product = PrismExplicitModel;
+
+    fn into_product(self) -> PrismExplicitModel {
+        self.to_model()
+    }
+}
@@ -0,0 +1,28 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+/**
+ * The contents of the files of a PRISM explicit model.
+ **/
+#[derive(Clone, PartialEq, Debug, Eq, Default)]
+pub struct PrismExplicitModel {
+    /** State valuations (.sta). **/
+    pub states: String,
+    /** Transitions with their probabilities (.tra). **/
+    pub transitions: String,
+    /** Labels (.lab). **/
+    pub labels: String,
+}
@@ -0,0 +1,42 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use graph_process_manager_core::process::config::AbstractProcessConfiguration;
+
+use crate::error::LoggerError;
+
+
+/**
+ * Converts the domain-specific parts of ExplorationEvents to and from bytes
+ * so that they can be recorded in a file and replayed later.
+ *
+ * Encoders append to the given buffer.
+ * Decoders receive exactly the bytes produced by the corresponding encoder.
+ **/
+pub trait ExplorationEventCodec<Conf : AbstractProcessConfiguration> {
+
+    fn encode_node(&self, node : &Conf::DomainSpecificNode, buffer : &mut Vec<u8>);
+
+    fn decode_node(&self, bytes : &[u8]) -> Result<Conf::DomainSpecificNode, LoggerError>;
+
+    fn encode_step(&self, step : &Conf::DomainSpecificStep, buffer : &mut Vec<u8>);
+
+    fn decode_step(&self, bytes : &[u8]) -> Result<Conf::DomainSpecificStep, LoggerError>;
+
+    fn encode_filtration_result(&self, filtration_result : &Conf::FiltrationResult, buffer : &mut Vec<u8>);
+
+    fn decode_filtration_result(&self, bytes : &[u8]) -> Result<Conf::FiltrationResult, LoggerError>;
+
+}
@@ -0,0 +1,103 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+/*
+ * Binary layout of a recording:
+ * - the MAGIC header
+ * - a sequence of records, each starting with a one byte tag
+ *   followed by LEB128-encoded unsigned integers and length-prefixed payloads:
+ *   + TAG_NEW_NODE               id, payload(node)
+ *   + TAG_NEW_STEP               origin id, target id, payload(step)
+ *   + TAG_FILTERED               parent id, payload(filtration result)
+ *   + TAG_NODE_WITHOUT_CHILDREN  id
+ *   + TAG_ALL_CHILDREN_PROCESSED parent id
+ */
+
+use std::io::{ErrorKind, Read, Write};
+
+
+pub(crate) const MAGIC : &[u8; 7] = b"GPMREC\x01";
+
+pub(crate) const TAG_NEW_NODE : u8 = 0;
+pub(crate) const TAG_NEW_STEP : u8 = 1;
+pub(crate) const TAG_FILTERED : u8 = 2;
+pub(crate) const TAG_NODE_WITHOUT_CHILDREN : u8 = 3;
+pub(crate) const TAG_ALL_CHILDREN_PROCESSED : u8 = 4;
+
+
+pub(crate) fn write_varint(writer : &mut impl Write, mut value : u64) -> std::io::Result<()> {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            return writer.write_all(&[byte]);
+        }
+        writer.write_all(&[byte | 0x80])?;
+    }
+}
+
+pub(crate) fn write_payload(writer : &mut impl Write, payload : &[u8]) -> std::io::Result<()> {
+    write_varint(writer, payload.len() as u64)?;
+    writer.write_all(payload)
+}
+
+
+/**
+ * Reads the tag of the next record, returning None at the end of the recording.
+ **/
+pub(crate) fn read_tag(reader : &mut impl Read) -> std::io::Result<Option<u8>> {
+    let mut byte = [0u8];
+    loop {
+        match reader.read(&mut byte) {
+            Ok(0) => return Ok(None),
+            Ok(_) => return Ok(Some(byte[0])),
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+pub(crate) fn read_varint(reader : &mut impl Read) -> std::io::Result<u64> {
+    let mut value : u64 = 0;
+    let mut shift = 0;
+    loop {
+        let mut byte = [0u8];
+        reader.read_exact(&mut byte)?;
+        if shift >= 64 {
+            return Err(std::io::Error::new(ErrorKind::InvalidData, "varint overflow"));
+        }
+        value |= ((byte[0] & 0x7f) as u64) << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(value);
+        }
+        shift += 7;
+    }
+}
+
+pub(crate) fn read_id(reader : &mut impl Read) -> std::io::Result<u32> {
+    u32::try_from(read_varint(reader)?)
+        .map_err(|_| std::io::Error::new(ErrorKind::InvalidData, "node id overflow"))
+}
+
+pub(crate) fn read_payload(reader : &mut impl Read) -> std::io::Result<Vec<u8>> {
+    let len = read_varint(reader)?;
+    // not pre-allocated from len so that a corrupted length fails on EOF instead of on allocation
+    let mut payload = Vec::new();
+    reader.take(len).read_to_end(&mut payload)?;
+    if payload.len() as u64 != len {
+        return Err(std::io::Error::new(ErrorKind::UnexpectedEof, "truncated payload"));
+    }
+    Ok(payload)
+}
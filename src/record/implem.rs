@@ -0,0 +1,145 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use graph_process_manager_core::process::config::AbstractProcessConfiguration;
+use graph_process_manager_core::process::manager::GenericProcessManager;
+
+use crate::control::{LoggerControl, TerminationReason};
+use crate::error::LoggerError;
+use crate::logger::{AbstractProcessLogger, LoggerResult};
+use crate::meta::EventMeta;
+use crate::record::codec::ExplorationEventCodec;
+use crate::record::format::*;
+use crate::record::logger::GenericEventRecorderLogger;
+use crate::util::create_folder;
+
+
+impl<Conf, Codec> GenericEventRecorderLogger<Conf, Codec> where
+    Conf : AbstractProcessConfiguration,
+    Codec : ExplorationEventCodec<Conf> {
+
+    /**
+     * Writes one record made of a tag, some ids and an optional payload
+     * (which is taken from the scratch buffer).
+     **/
+    fn write_record(&mut self, tag : u8, ids : &[u32], with_payload : bool) -> LoggerResult {
+        let writer = self.writer.as_mut().ok_or_else(|| {
+            LoggerError::Custom("event received before the recording was initialized".to_string())
+        })?;
+        let mut write = || -> std::io::Result<()> {
+            writer.write_all(&[tag])?;
+            for id in ids {
+                write_varint(writer, *id as u64)?;
+            }
+            if with_payload {
+                write_payload(writer, &self.buffer)?;
+            }
+            Ok(())
+        };
+        write().map_err(|e| {
+            LoggerError::io(format!("writing recording '{}'", self.get_recording_path().display()), e)
+        })?;
+        Ok(LoggerControl::Continue)
+    }
+}
+
+
+impl<Conf, Codec> AbstractProcessLogger<Conf> for GenericEventRecorderLogger<Conf, Codec>
+where
+    Conf : AbstractProcessConfiguration + 'static,
+    Codec : ExplorationEventCodec<Conf> + 'static,
+{
+    fn log_initialize_process(&mut self, _manager: &GenericProcessManager<Conf>) -> LoggerResult {
+        create_folder(&self.parent_folder)?;
+        let path = self.get_recording_path();
+        let context = format!("creating recording '{}'", path.display());
+        let mut writer = BufWriter::new(File::create(&path).map_err(|e| LoggerError::io(context.clone(), e))?);
+        writer.write_all(MAGIC).map_err(|e| LoggerError::io(context, e))?;
+        self.writer = Some(writer);
+        Ok(LoggerControl::Continue)
+    }
+
+    fn log_new_node(
+        &mut self,
+        _context_and_param: &Conf::ContextAndParameterization,
+        _meta: &EventMeta<Conf>,
+        new_node_id: u32,
+        new_node: &Conf::DomainSpecificNode,
+    ) -> LoggerResult {
+        self.buffer.clear();
+        self.codec.encode_node(new_node, &mut self.buffer);
+        self.write_record(TAG_NEW_NODE, &[new_node_id], true)
+    }
+
+    fn log_new_step(
+        &mut self,
+        _context_and_param: &Conf::ContextAndParameterization,
+        _meta: &EventMeta<Conf>,
+        origin_node_id: u32,
+        step: &Conf::DomainSpecificStep,
+        target_node_id: u32,
+        _target_node: &Conf::DomainSpecificNode,
+    ) -> LoggerResult {
+        self.buffer.clear();
+        self.codec.encode_step(step, &mut self.buffer);
+        self.write_record(TAG_NEW_STEP, &[origin_node_id, target_node_id], true)
+    }
+
+    fn log_all_children_processed(
+        &mut self,
+        _context_and_param: &Conf::ContextAndParameterization,
+        _meta: &EventMeta<Conf>,
+        parent_node_id: u32,
+    ) -> LoggerResult {
+        self.write_record(TAG_ALL_CHILDREN_PROCESSED, &[parent_node_id], false)
+    }
+
+    fn log_notify_node_without_children(
+        &mut self,
+        _context_and_param: &Conf::ContextAndParameterization,
+        _meta: &EventMeta<Conf>,
+        node_id: u32,
+    ) -> LoggerResult {
+        self.write_record(TAG_NODE_WITHOUT_CHILDREN, &[node_id], false)
+    }
+
+    fn log_filtered(
+        &mut self,
+        _context_and_param: &Conf::ContextAndParameterization,
+        _meta: &EventMeta<Conf>,
+        parent_node_id: u32,
+        filtration_result: &Conf::FiltrationResult,
+    ) -> LoggerResult {
+        self.buffer.clear();
+        self.codec.encode_filtration_result(filtration_result, &mut self.buffer);
+        self.write_record(TAG_FILTERED, &[parent_node_id], true)
+    }
+
+    fn log_terminate_process(
+        &mut self,
+        _manager: &GenericProcessManager<Conf>,
+        _reason: &TerminationReason,
+    ) -> LoggerResult {
+        if let Some(mut writer) = self.writer.take() {
+            writer.flush().map_err(|e| {
+                LoggerError::io(format!("writing recording '{}'", self.get_recording_path().display()), e)
+            })?;
+        }
+        Ok(LoggerControl::Continue)
+    }
+}
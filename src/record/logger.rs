@@ -0,0 +1,69 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+
+use graph_process_manager_core::process::config::AbstractProcessConfiguration;
+
+use crate::record::codec::ExplorationEventCodec;
+
+
+/**
+ * Logger that records every ExplorationEvent it receives into a compact binary file.
+ * The file can later be fed to any set of loggers with replay_loggers,
+ * without running the process again.
+ **/
+pub struct GenericEventRecorderLogger<Conf, Codec>
+    where
+        Conf : AbstractProcessConfiguration,
+        Codec : ExplorationEventCodec<Conf>
+        {
+    // ***
+    phantom : std::marker::PhantomData<Conf>,
+    pub codec : Codec,
+    // ***
+    pub(crate) parent_folder : String,
+    pub(crate) file_name : String,
+    // ***
+    // opened when the process is initialized and closed when it terminates
+    pub(crate) writer : Option<BufWriter<File>>,
+    // scratch buffer reused to encode domain-specific payloads
+    pub(crate) buffer : Vec<u8>
+}
+
+impl<Conf, Codec> GenericEventRecorderLogger<Conf, Codec> where
+    Conf : AbstractProcessConfiguration,
+    Codec : ExplorationEventCodec<Conf> {
+
+    pub fn new(codec : Codec,
+               parent_folder : String,
+               file_name : String) -> Self {
+        Self {
+            phantom : std::marker::PhantomData,
+            codec,
+            parent_folder,
+            file_name,
+            writer : None,
+            buffer : vec![]
+        }
+    }
+
+    pub fn get_recording_path(&self) -> PathBuf {
+        [&self.parent_folder, &self.file_name].iter().collect()
+    }
+}
@@ -0,0 +1,134 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+use std::sync::Arc;
+
+use graph_process_manager_core::process::config::AbstractProcessConfiguration;
+use graph_process_manager_core::process::event::ExplorationEvent;
+use graph_process_manager_core::process::manager::GenericProcessManager;
+
+use crate::dispatch::{borrow_loggers, LoggersDispatcher};
+use crate::error::LoggerError;
+use crate::logger::{AbstractProcessLogger, DriveLoggersOptions};
+use crate::record::codec::ExplorationEventCodec;
+use crate::record::format::*;
+use crate::report::LoggersRunReport;
+
+
+/**
+ * Feeds a recording made by a GenericEventRecorderLogger to a set of loggers,
+ * exactly as drive_loggers_with_options would have done during the recorded exploration
+ * (same target_node registry, error handling, supervision and LoggerControl semantics).
+ *
+ * The manager is not iterated. It provides the context and parameterization
+ * and is given to log_initialize_process and log_terminate_process,
+ * so it should be built with the same configuration as the recorded one.
+ * Note that its global state is the initial one and not the one reached by the recorded run.
+ *
+ * Returns an error if the recording cannot be read or decoded, in which case
+ * log_terminate_process is not called.
+ **/
+pub fn replay_loggers<Conf, Codec>(
+    manager: &GenericProcessManager<Conf>,
+    recording_path: &Path,
+    codec: &Codec,
+    loggers: &mut [Box<dyn AbstractProcessLogger<Conf> + '_>],
+    options: &DriveLoggersOptions,
+) -> Result<LoggersRunReport, LoggerError>
+where
+    Conf: AbstractProcessConfiguration + 'static,
+    Codec: ExplorationEventCodec<Conf>,
+{
+    let context = format!("reading recording '{}'", recording_path.display());
+    let file = File::open(recording_path).map_err(|e| LoggerError::io(context.clone(), e))?;
+    let mut reader = BufReader::new(file);
+    let mut magic = [0u8; MAGIC.len()];
+    reader.read_exact(&mut magic).map_err(|e| LoggerError::io(context.clone(), e))?;
+    if &magic != MAGIC {
+        return Err(LoggerError::Custom(format!("{} : not a recording", context)));
+    }
+
+    let mut dispatcher = LoggersDispatcher::new(borrow_loggers(loggers), options.node_registry_policy)
+        .with_supervision(options.supervised);
+    dispatcher.initialize(manager);
+    while dispatcher.wants_more_events() {
+        let event = read_event(&mut reader, codec).map_err(|e| match e {
+            RecordReadError::Io(e) => LoggerError::io(context.clone(), e),
+            RecordReadError::Decode(e) => e,
+        })?;
+        let Some(event) = event else { break };
+        dispatcher.handle_event(&manager.context_and_param, &event);
+    }
+    Ok(dispatcher.terminate(manager))
+}
+
+
+enum RecordReadError {
+    Io(std::io::Error),
+    Decode(LoggerError),
+}
+
+impl From<std::io::Error> for RecordReadError {
+    fn from(e: std::io::Error) -> Self {
+        RecordReadError::Io(e)
+    }
+}
+
+
+fn read_event<Conf, Codec>(
+    reader: &mut impl Read,
+    codec: &Codec,
+) -> Result<Option<ExplorationEvent<Conf>>, RecordReadError>
+where
+    Conf: AbstractProcessConfiguration,
+    Codec: ExplorationEventCodec<Conf>,
+{
+    let Some(tag) = read_tag(reader)? else { return Ok(None) };
+    let event = match tag {
+        TAG_NEW_NODE => {
+            let id = read_id(reader)?;
+            let node = codec.decode_node(&read_payload(reader)?).map_err(RecordReadError::Decode)?;
+            ExplorationEvent::NewNode { id, node: Arc::new(node) }
+        }
+        TAG_NEW_STEP => {
+            let origin_node_id = read_id(reader)?;
+            let target_node_id = read_id(reader)?;
+            let step = codec.decode_step(&read_payload(reader)?).map_err(RecordReadError::Decode)?;
+            ExplorationEvent::NewStep { origin_node_id, step, target_node_id }
+        }
+        TAG_FILTERED => {
+            let parent_node_id = read_id(reader)?;
+            let filtration_result = codec
+                .decode_filtration_result(&read_payload(reader)?)
+                .map_err(RecordReadError::Decode)?;
+            ExplorationEvent::Filtered { parent_node_id, filtration_result }
+        }
+        TAG_NODE_WITHOUT_CHILDREN => {
+            ExplorationEvent::NodeWithoutChildren { node_id: read_id(reader)? }
+        }
+        TAG_ALL_CHILDREN_PROCESSED => {
+            ExplorationEvent::AllChildrenProcessed { parent_node_id: read_id(reader)? }
+        }
+        unknown => {
+            return Err(RecordReadError::Decode(LoggerError::Custom(
+                format!("unknown record tag {} in recording", unknown),
+            )));
+        }
+    };
+    Ok(Some(event))
+}
@@ -0,0 +1,232 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+
+/**
+ * How long the id→node registry of the drivers keeps the nodes it has seen.
+ *
+ * The registry is used to supply target_node to log_new_step.
+ * A NewStep event whose target is no longer in the registry is not dispatched,
+ * so policies other than KeepAll and Automatic are a trade-off between memory and
+ * the completeness of the steps towards already-known nodes (back-edges) in memoized runs.
+ **/
+#[derive(Clone, Copy, PartialEq, Debug, Eq, Hash, Default)]
+pub enum NodeRegistryPolicy {
+    /**
+     * DropCompletedNodes when the process is not memoized
+     * (in which case no step is lost), KeepAll otherwise.
+     **/
+    #[default]
+    Automatic,
+    /** Keeps every node until the end of the run. **/
+    KeepAll,
+    /**
+     * Drops a node once it is complete (AllChildrenProcessed or NodeWithoutChildren)
+     * and the step leading to it has been dispatched.
+     * Without memoization, no later step can target it.
+     **/
+    DropCompletedNodes,
+    /** Keeps at most that many nodes, dropping the least recently used ones. **/
+    LeastRecentlyUsed(usize),
+    /**
+     * Keeps only the nodes whose depth is at least that of the deepest discovered node minus
+     * that window. The depth of a node is that of the node from which it was discovered plus one.
+     **/
+    DepthWindow(u32),
+}
+
+impl NodeRegistryPolicy {
+    pub(crate) fn resolve(self, is_memoized: bool) -> Self {
+        match self {
+            NodeRegistryPolicy::Automatic if is_memoized => NodeRegistryPolicy::KeepAll,
+            NodeRegistryPolicy::Automatic => NodeRegistryPolicy::DropCompletedNodes,
+            other => other,
+        }
+    }
+}
+
+
+/** What the registry knows of a node, besides the node itself. **/
+#[derive(Clone, Copy, PartialEq, Debug, Eq)]
+pub(crate) struct NodeInfo {
+    pub depth: Option<u32>,
+    pub parent_node_id: Option<u32>,
+    pub discovery_index: u32,
+}
+
+
+struct RegisteredNode<Node> {
+    node: Arc<Node>,
+    info: NodeInfo,
+    // for LeastRecentlyUsed
+    last_use: u64,
+    // for DropCompletedNodes
+    completed: bool,
+    reached_by_step: bool,
+}
+
+
+/**
+ * The id→node registry of the drivers, bounded according to a NodeRegistryPolicy.
+ **/
+pub(crate) struct NodeRegistry<Node> {
+    policy: NodeRegistryPolicy,
+    nodes: HashMap<u32, RegisteredNode<Node>>,
+    discovered: u32,
+    // LeastRecentlyUsed bookkeeping : last use tick → node id
+    clock: u64,
+    uses: BTreeMap<u64, u32>,
+    // DepthWindow bookkeeping : depth → ids of the nodes at that depth
+    depths: BTreeMap<u32, Vec<u32>>,
+    max_depth: u32,
+}
+
+impl<Node> NodeRegistry<Node> {
+
+    pub fn new(policy: NodeRegistryPolicy) -> Self {
+        Self {
+            policy,
+            nodes: HashMap::new(),
+            discovered: 0,
+            clock: 0,
+            uses: BTreeMap::new(),
+            depths: BTreeMap::new(),
+            max_depth: 0,
+        }
+    }
+
+    /** To be called once it is known whether the process is memoized, before any node is inserted. **/
+    pub fn resolve_policy(&mut self, is_memoized: bool) {
+        self.policy = self.policy.resolve(is_memoized);
+    }
+
+    /** Whether the next new node is the initial node. **/
+    pub fn expects_initial_node(&self) -> bool {
+        self.discovered == 0
+    }
+
+    pub fn get_node(&self, id: u32) -> Option<Arc<Node>> {
+        self.nodes.get(&id).map(|registered| Arc::clone(&registered.node))
+    }
+
+    pub fn get_info(&self, id: u32) -> Option<NodeInfo> {
+        self.nodes.get(&id).map(|registered| registered.info)
+    }
+
+    /**
+     * Registers a new node, discovered from parent_node_id
+     * (None for the initial node, or if the step leading to it is unknown).
+     **/
+    pub fn on_new_node(&mut self, id: u32, node: &Arc<Node>, parent_node_id: Option<u32>) -> NodeInfo {
+        // the initial node is the only one that is not reached by a step
+        let is_initial = self.expects_initial_node();
+        let depth = if is_initial {
+            Some(0)
+        } else {
+            parent_node_id
+                .and_then(|parent| self.get_info(parent))
+                .and_then(|parent| parent.depth)
+                .map(|depth| depth + 1)
+        };
+        let info = NodeInfo { depth, parent_node_id, discovery_index: self.discovered };
+        self.discovered += 1;
+        self.nodes.insert(id, RegisteredNode {
+            node: Arc::clone(node),
+            info,
+            last_use: 0,
+            completed: false,
+            reached_by_step: is_initial,
+        });
+        if let NodeRegistryPolicy::DepthWindow(_) = self.policy {
+            // the parent of a node of unknown depth has already left the window,
+            // the node itself is kept as if it was among the deepest ones
+            let depth = depth.unwrap_or(self.max_depth);
+            self.depths.entry(depth).or_default().push(id);
+            self.max_depth = self.max_depth.max(depth);
+        }
+        self.touch(id);
+        self.evict();
+        info
+    }
+
+    /**
+     * Returns the target of a step if it is still registered,
+     * and updates the registry to account for that step.
+     **/
+    pub fn on_new_step(&mut self, origin_node_id: u32, target_node_id: u32) -> Option<Arc<Node>> {
+        let target = self.nodes.get_mut(&target_node_id)?;
+        target.reached_by_step = true;
+        let target_node = Arc::clone(&target.node);
+        self.touch(origin_node_id);
+        self.touch(target_node_id);
+        self.drop_if_done(target_node_id);
+        self.evict();
+        Some(target_node)
+    }
+
+    /** To be called on AllChildrenProcessed and NodeWithoutChildren. **/
+    pub fn on_node_completed(&mut self, node_id: u32) {
+        if let Some(registered) = self.nodes.get_mut(&node_id) {
+            registered.completed = true;
+            self.drop_if_done(node_id);
+        }
+    }
+
+    fn touch(&mut self, id: u32) {
+        if let NodeRegistryPolicy::LeastRecentlyUsed(_) = self.policy {
+            if let Some(registered) = self.nodes.get_mut(&id) {
+                self.uses.remove(&registered.last_use);
+                self.clock += 1;
+                registered.last_use = self.clock;
+                self.uses.insert(self.clock, id);
+            }
+        }
+    }
+
+    fn drop_if_done(&mut self, id: u32) {
+        if let NodeRegistryPolicy::DropCompletedNodes = self.policy {
+            if self.nodes.get(&id).is_some_and(|registered| registered.completed && registered.reached_by_step) {
+                self.nodes.remove(&id);
+            }
+        }
+    }
+
+    fn evict(&mut self) {
+        match self.policy {
+            NodeRegistryPolicy::LeastRecentlyUsed(capacity) => {
+                // the most recently used node is always kept
+                while self.nodes.len() > capacity.max(1) {
+                    let Some((_, id)) = self.uses.pop_first() else { break };
+                    self.nodes.remove(&id);
+                }
+            }
+            NodeRegistryPolicy::DepthWindow(window) => {
+                while let Some(entry) = self.depths.first_entry() {
+                    if entry.key().saturating_add(window) >= self.max_depth {
+                        break;
+                    }
+                    for id in entry.remove() {
+                        self.nodes.remove(&id);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,174 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::control::TerminationReason;
+use crate::error::LoggerError;
+
+
+/** The logger callback during which a failure occurred. **/
+#[derive(Clone, Copy, PartialEq, Debug, Eq, Hash)]
+pub enum LoggerEventKind {
+    InitializeProcess,
+    NewNode {
+        node_id: u32,
+    },
+    NewStep {
+        origin_node_id: u32,
+        target_node_id: u32,
+    },
+    AllChildrenProcessed {
+        parent_node_id: u32,
+    },
+    NodeWithoutChildren {
+        node_id: u32,
+    },
+    Filtered {
+        parent_node_id: u32,
+    },
+    TerminateProcess,
+}
+
+impl fmt::Display for LoggerEventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoggerEventKind::InitializeProcess => write!(f, "initialize process"),
+            LoggerEventKind::NewNode { node_id } => write!(f, "new node {}", node_id),
+            LoggerEventKind::NewStep { origin_node_id, target_node_id } => {
+                write!(f, "new step {} -> {}", origin_node_id, target_node_id)
+            }
+            LoggerEventKind::AllChildrenProcessed { parent_node_id } => {
+                write!(f, "all children of {} processed", parent_node_id)
+            }
+            LoggerEventKind::NodeWithoutChildren { node_id } => {
+                write!(f, "node {} without children", node_id)
+            }
+            LoggerEventKind::Filtered { parent_node_id } => {
+                write!(f, "filtered from {}", parent_node_id)
+            }
+            LoggerEventKind::TerminateProcess => write!(f, "terminate process"),
+        }
+    }
+}
+
+
+/** A logger callback that returned an error. **/
+#[derive(Debug)]
+pub struct LoggerFailure {
+    /** Position of the failing logger in the slice given to drive_loggers. **/
+    pub logger_index: usize,
+    pub logger_name: String,
+    pub event: LoggerEventKind,
+    pub error: LoggerError,
+}
+
+impl fmt::Display for LoggerFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "logger #{} ({}) failed on {} : {}",
+            self.logger_index, self.logger_name, self.event, self.error
+        )
+    }
+}
+
+
+/** A logger callback that panicked, in supervised mode. **/
+#[derive(Clone, PartialEq, Debug, Eq)]
+pub struct LoggerPanic {
+    /** Position of the panicking logger in the slice given to drive_loggers. **/
+    pub logger_index: usize,
+    pub logger_name: String,
+    pub event: LoggerEventKind,
+    pub message: String,
+}
+
+impl fmt::Display for LoggerPanic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "logger #{} ({}) panicked on {} : {}",
+            self.logger_index, self.logger_name, self.event, self.message
+        )
+    }
+}
+
+
+/**
+ * Summary of a run of drive_loggers.
+ *
+ * A logger stopping the exploration early is not a failure:
+ * it is only reflected in termination.
+ *
+ * A logger that fails (or panics, in supervised mode) is disabled for the remainder of the run
+ * (including log_terminate_process), so each logger appears at most once in failures and panics.
+ **/
+#[derive(Debug, Default)]
+pub struct LoggersRunReport {
+    pub failures: Vec<LoggerFailure>,
+    /** Panics caught in supervised mode. **/
+    pub panics: Vec<LoggerPanic>,
+    pub termination: TerminationReason,
+    /** Number of events that were not delivered to a logger, by logger index (threaded drivers only). **/
+    pub dropped_events: BTreeMap<usize, u64>,
+}
+
+impl LoggersRunReport {
+    pub fn is_success(&self) -> bool {
+        self.failures.is_empty() && self.panics.is_empty()
+    }
+
+    /** Adds the failures, panics and dropped events of a report on another subset of the loggers. **/
+    pub(crate) fn absorb(&mut self, other: LoggersRunReport) {
+        self.failures.extend(other.failures);
+        self.panics.extend(other.panics);
+        self.dropped_events.extend(other.dropped_events);
+    }
+
+    /** Turns the report into an error if any logger failed or panicked, e.g. to propagate it with `?`. **/
+    pub fn into_result(self) -> Result<(), LoggersRunReport> {
+        if self.is_success() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl fmt::Display for LoggersRunReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_success() {
+            return write!(f, "all loggers succeeded");
+        }
+        if !self.failures.is_empty() {
+            writeln!(f, "{} logger(s) failed:", self.failures.len())?;
+            for failure in &self.failures {
+                writeln!(f, "  {}", failure)?;
+            }
+        }
+        if !self.panics.is_empty() {
+            writeln!(f, "{} logger(s) panicked:", self.panics.len())?;
+            for panic in &self.panics {
+                writeln!(f, "  {}", panic)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for LoggersRunReport {}
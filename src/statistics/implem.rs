@@ -0,0 +1,134 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::fs;
+
+use graph_process_manager_core::process::config::AbstractProcessConfiguration;
+use graph_process_manager_core::process::manager::GenericProcessManager;
+
+use crate::control::{LoggerControl, TerminationReason};
+use crate::error::LoggerError;
+use crate::logger::{AbstractProcessLogger, LoggerResult};
+use crate::meta::EventMeta;
+use crate::statistics::logger::GenericStatisticsLogger;
+use crate::util::create_folder;
+
+
+impl<Conf: AbstractProcessConfiguration> GenericStatisticsLogger<Conf> {
+
+    fn on_node_completed(&mut self, node_id: u32) {
+        if let Some(out_degree) = self.frontier_out_degrees.remove(&node_id) {
+            *self.statistics.out_degree_histogram.entry(out_degree).or_default() += 1;
+        }
+    }
+}
+
+
+impl<Conf: AbstractProcessConfiguration + 'static> AbstractProcessLogger<Conf> for GenericStatisticsLogger<Conf> {
+
+    fn log_new_node(
+        &mut self,
+        _context_and_param: &Conf::ContextAndParameterization,
+        meta: &EventMeta<Conf>,
+        new_node_id: u32,
+        _new_node: &Conf::DomainSpecificNode,
+    ) -> LoggerResult {
+        let statistics = &mut self.statistics;
+        statistics.nodes += 1;
+        statistics.elapsed = meta.elapsed;
+        if let Some(depth) = meta.depth {
+            *statistics.depth_histogram.entry(depth).or_default() += 1;
+        }
+        self.frontier_out_degrees.insert(new_node_id, 0);
+        statistics.max_frontier_size = statistics.max_frontier_size.max(self.frontier_out_degrees.len() as u64);
+        self.last_new_node_id = Some(new_node_id);
+        Ok(LoggerControl::Continue)
+    }
+
+    fn log_new_step(
+        &mut self,
+        _context_and_param: &Conf::ContextAndParameterization,
+        meta: &EventMeta<Conf>,
+        origin_node_id: u32,
+        _step: &Conf::DomainSpecificStep,
+        target_node_id: u32,
+        _target_node: &Conf::DomainSpecificNode,
+    ) -> LoggerResult {
+        self.statistics.steps += 1;
+        self.statistics.elapsed = meta.elapsed;
+        if self.last_new_node_id.take() != Some(target_node_id) {
+            self.statistics.back_edges += 1;
+        }
+        if let Some(out_degree) = self.frontier_out_degrees.get_mut(&origin_node_id) {
+            *out_degree += 1;
+        }
+        Ok(LoggerControl::Continue)
+    }
+
+    fn log_all_children_processed(
+        &mut self,
+        _context_and_param: &Conf::ContextAndParameterization,
+        meta: &EventMeta<Conf>,
+        parent_node_id: u32,
+    ) -> LoggerResult {
+        self.statistics.elapsed = meta.elapsed;
+        self.on_node_completed(parent_node_id);
+        Ok(LoggerControl::Continue)
+    }
+
+    fn log_notify_node_without_children(
+        &mut self,
+        _context_and_param: &Conf::ContextAndParameterization,
+        meta: &EventMeta<Conf>,
+        node_id: u32,
+    ) -> LoggerResult {
+        self.statistics.leaves += 1;
+        self.statistics.elapsed = meta.elapsed;
+        self.on_node_completed(node_id);
+        Ok(LoggerControl::Continue)
+    }
+
+    fn log_filtered(
+        &mut self,
+        _context_and_param: &Conf::ContextAndParameterization,
+        meta: &EventMeta<Conf>,
+        _parent_node_id: u32,
+        _filtration_result: &Conf::FiltrationResult,
+    ) -> LoggerResult {
+        self.statistics.filtrations += 1;
+        self.statistics.elapsed = meta.elapsed;
+        Ok(LoggerControl::Continue)
+    }
+
+    fn log_terminate_process(
+        &mut self,
+        _manager: &GenericProcessManager<Conf>,
+        reason: &TerminationReason,
+    ) -> LoggerResult {
+        self.statistics.termination = Some(reason.to_string());
+        create_folder(&self.parent_folder)?;
+        for (suffix, content) in [
+            (".json", self.statistics.to_json()),
+            ("_depth.csv", self.statistics.depth_histogram_to_csv()),
+            ("_out_degree.csv", self.statistics.out_degree_histogram_to_csv()),
+        ] {
+            let path = self.get_output_path(suffix);
+            fs::write(&path, content)
+                .map_err(|e| LoggerError::io(format!("writing '{}'", path.display()), e))?;
+        }
+        Ok(LoggerControl::Continue)
+    }
+}
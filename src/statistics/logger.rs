@@ -0,0 +1,75 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use graph_process_manager_core::process::config::AbstractProcessConfiguration;
+
+use crate::logger_registry::LoggerWithProduct;
+use crate::statistics::summary::ExplorationStatistics;
+
+
+/**
+ * Logger that collects ExplorationStatistics.
+ * When the process terminates, it writes in parent_folder:
+ * - a JSON summary "{name}.json",
+ * - the depth histogram as "{name}_depth.csv",
+ * - the out-degree histogram as "{name}_out_degree.csv".
+ **/
+pub struct GenericStatisticsLogger<Conf : AbstractProcessConfiguration> {
+    phantom : std::marker::PhantomData<Conf>,
+    // ***
+    pub(crate) parent_folder : String,
+    pub(crate) name : String,
+    // ***
+    pub(crate) statistics : ExplorationStatistics,
+    // number of outgoing steps of the nodes that are discovered but not yet completely processed
+    pub(crate) frontier_out_degrees : HashMap<u32,u32>,
+    // NewNode event immediately preceding the current one, as a new node is followed by the step leading to it
+    pub(crate) last_new_node_id : Option<u32>
+}
+
+impl<Conf: AbstractProcessConfiguration> GenericStatisticsLogger<Conf> {
+
+    pub fn new(parent_folder : String, name : String) -> Self {
+        Self {
+            phantom : std::marker::PhantomData,
+            parent_folder,
+            name,
+            statistics : ExplorationStatistics::default(),
+            frontier_out_degrees : HashMap::new(),
+            last_new_node_id : None
+        }
+    }
+
+    pub fn get_statistics(&self) -> &ExplorationStatistics {
+        &self.statistics
+    }
+
+    pub(crate) fn get_output_path(&self, suffix : &str) -> PathBuf {
+        [&self.parent_folder, &format!("{}{}", self.name, suffix)].iter().collect()
+    }
+}
+
+
+impl<Conf: AbstractProcessConfiguration> LoggerWithProduct for GenericStatisticsLogger<Conf> {
+    type Product = ExplorationStatistics;
+
+    fn into_product(self) -> ExplorationStatistics {
+        self.statistics
+    }
+}
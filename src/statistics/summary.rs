@@ -0,0 +1,118 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::time::Duration;
+
+
+/**
+ * Figures collected by the GenericStatisticsLogger over an exploration.
+ **/
+#[derive(Clone, PartialEq, Debug, Eq, Default)]
+pub struct ExplorationStatistics {
+    pub nodes: u64,
+    pub steps: u64,
+    pub filtrations: u64,
+    /** Nodes that were notified as having no children. **/
+    pub leaves: u64,
+    /** Steps whose target node had already been discovered (memoized runs only). **/
+    pub back_edges: u64,
+    /** Greatest number of nodes discovered but not yet completely processed at the same time. **/
+    pub max_frontier_size: u64,
+    /** Number of nodes by depth (nodes of unknown depth are not counted). **/
+    pub depth_histogram: BTreeMap<u32, u64>,
+    /**
+     * Number of nodes by number of outgoing steps,
+     * for the nodes whose processing was complete when the exploration ended.
+     **/
+    pub out_degree_histogram: BTreeMap<u32, u64>,
+    /** Time elapsed between the initialization of the loggers and the last event. **/
+    pub elapsed: Duration,
+    /** Description of the TerminationReason, once the exploration has ended. **/
+    pub termination: Option<String>,
+}
+
+impl ExplorationStatistics {
+
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        json.push_str("{\n");
+        for (key, value) in [
+            ("nodes", self.nodes),
+            ("steps", self.steps),
+            ("filtrations", self.filtrations),
+            ("leaves", self.leaves),
+            ("back_edges", self.back_edges),
+            ("max_frontier_size", self.max_frontier_size),
+        ] {
+            let _ = writeln!(json, "  \"{}\": {},", key, value);
+        }
+        let _ = writeln!(json, "  \"max_depth\": {},",
+            self.depth_histogram.keys().next_back().map_or("null".to_string(), |depth| depth.to_string()));
+        let _ = writeln!(json, "  \"elapsed_ms\": {},", self.elapsed.as_millis());
+        let _ = writeln!(json, "  \"termination\": {},",
+            self.termination.as_deref().map_or("null".to_string(), json_string));
+        let _ = writeln!(json, "  \"depth_histogram\": {},", json_histogram(&self.depth_histogram));
+        let _ = writeln!(json, "  \"out_degree_histogram\": {}", json_histogram(&self.out_degree_histogram));
+        json.push('}');
+        json
+    }
+
+    pub fn depth_histogram_to_csv(&self) -> String {
+        csv_histogram("depth", &self.depth_histogram)
+    }
+
+    pub fn out_degree_histogram_to_csv(&self) -> String {
+        csv_histogram("out_degree", &self.out_degree_histogram)
+    }
+}
+
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"'  => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+// keys are written as strings, as JSON objects require
+fn json_histogram(histogram: &BTreeMap<u32, u64>) -> String {
+    let entries: Vec<String> = histogram
+        .iter()
+        .map(|(key, count)| format!("\"{}\": {}", key, count))
+        .collect();
+    format!("{{{}}}", entries.join(", "))
+}
+
+fn csv_histogram(key_column: &str, histogram: &BTreeMap<u32, u64>) -> String {
+    let mut csv = format!("{},nodes\n", key_column);
+    for (key, count) in histogram {
+        let _ = writeln!(csv, "{},{}", key, count);
+    }
+    csv
+}
@@ -15,49 +15,57 @@ limitations under the License.
 */
 
 use std::collections::HashSet;
-use std::fs;
 use std::path::PathBuf;
 
 use graph_process_manager_core::process::config::AbstractProcessConfiguration;
 use graph_process_manager_core::process::manager::GenericProcessManager;
 
-use crate::logger::AbstractProcessLogger;
+use crate::control::LoggerControl;
+use crate::error::LoggerError;
+use crate::logger::{AbstractProcessLogger, LoggerResult};
+use crate::meta::EventMeta;
 use crate::stepstrace::logger::GenericStepsTraceLogger;
 use crate::stepstrace::object::ObjectToBuildWhenTracingSteps;
+use crate::util::reset_folder;
 
 
 impl<Conf: AbstractProcessConfiguration + 'static,
      ObjectToBuild: ObjectToBuildWhenTracingSteps + 'static>
     AbstractProcessLogger<Conf> for GenericStepsTraceLogger<Conf, ObjectToBuild>
 {
-    fn log_initialize_process(&mut self, _manager: &GenericProcessManager<Conf>) {
-        let _ = fs::remove_dir_all(&self.parent_folder);
-        let _ = fs::create_dir_all(&self.parent_folder);
+    fn log_initialize_process(&mut self, _manager: &GenericProcessManager<Conf>) -> LoggerResult {
+        reset_folder(&self.parent_folder)?;
+        Ok(LoggerControl::Continue)
     }
 
     fn log_new_node(
         &mut self,
         context_and_param: &Conf::ContextAndParameterization,
+        meta: &EventMeta<Conf>,
         new_node_id: u32,
         new_node: &Conf::DomainSpecificNode,
-    ) {
-        if new_node_id == 1 {
+    ) -> LoggerResult {
+        if meta.depth == Some(0) {
             let initial = self.printer.get_initial_object(context_and_param, new_node);
-            self.trace_map.insert(1, std::iter::once(initial).collect());
+            self.trace_map.insert(new_node_id, std::iter::once(initial).collect());
         } else {
             self.trace_map.insert(new_node_id, HashSet::new());
         }
+        Ok(LoggerControl::Continue)
     }
 
     fn log_new_step(
         &mut self,
         context_and_param: &Conf::ContextAndParameterization,
+        _meta: &EventMeta<Conf>,
         origin_node_id: u32,
         step: &Conf::DomainSpecificStep,
         target_node_id: u32,
         target_node: &Conf::DomainSpecificNode,
-    ) {
-        let parent_objects = self.trace_map.get(&origin_node_id).unwrap();
+    ) -> LoggerResult {
+        let parent_objects = self.trace_map.get(&origin_node_id).ok_or_else(|| {
+            LoggerError::Custom(format!("no trace recorded for the origin node {} of a step", origin_node_id))
+        })?;
         let objects_from_step: HashSet<ObjectToBuild> = parent_objects
             .iter()
             .map(|o| self.printer.add_step_to_object(context_and_param, o, step))
@@ -73,21 +81,29 @@ impl<Conf: AbstractProcessConfiguration + 'static,
                 self.trace_counter += 1;
                 let file_name = format!("{}{}.{}", self.prefix, self.trace_counter, self.file_extension);
                 let path_buf: PathBuf = [&self.parent_folder, &file_name].iter().collect();
-                self.printer.print_object(context_and_param, o, path_buf.as_path());
+                self.printer.print_object(context_and_param, o, path_buf.as_path())?;
                 if let Some(memo) = &mut self.anti_duplication_memoizer {
                     memo.insert(o.clone());
                 }
             }
         }
 
-        self.trace_map.get_mut(&target_node_id).unwrap().extend(new_objects);
+        self.trace_map
+            .get_mut(&target_node_id)
+            .ok_or_else(|| {
+                LoggerError::Custom(format!("no trace recorded for the target node {} of a step", target_node_id))
+            })?
+            .extend(new_objects);
+        Ok(LoggerControl::Continue)
     }
 
     fn log_all_children_processed(
         &mut self,
         _context_and_param: &Conf::ContextAndParameterization,
+        _meta: &EventMeta<Conf>,
         parent_node_id: u32,
-    ) {
+    ) -> LoggerResult {
         self.trace_map.remove(&parent_node_id);
+        Ok(LoggerControl::Continue)
     }
 }
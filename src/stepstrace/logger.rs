@@ -34,8 +34,8 @@ pub struct GenericStepsTraceLogger<Conf : AbstractProcessConfiguration,
     // if we want to avoid generate duplicated object, this will store already printed ones to avoid this
     pub(crate) anti_duplication_memoizer : Option<HashSet<ObjectToBuild>>,
     // maps node id u32 to objects to build
-    // the initial node of id 1 has an initial object
-    // subsequents objects are build progressively once nodes are reached from the initial node of id 1
+    // the initial node (of depth 0) has an initial object
+    // subsequents objects are build progressively once nodes are reached from the initial node
     // there may be several objects associated to a single node id
     // because there may be several distinct paths to the same node if memoization is used in the process
     pub(crate) trace_map : HashMap<u32,HashSet<ObjectToBuild>>,
@@ -19,6 +19,7 @@ limitations under the License.
 use std::path::Path;
 use graph_process_manager_core::process::config::AbstractProcessConfiguration;
 
+use crate::error::LoggerError;
 use crate::stepstrace::object::ObjectToBuildWhenTracingSteps;
 
 
@@ -43,10 +44,14 @@ pub trait StepsTraceProcessPrinter<Conf : AbstractProcessConfiguration, ObjectTo
         node: &Conf::DomainSpecificNode
     ) -> bool;
 
+    /**
+     * Prints the object into the file at path.
+     * An error is reported by the logger, which then receives no more events.
+     **/
     fn print_object(
         &self,
         context_and_param: &Conf::ContextAndParameterization,
         object : &ObjectToBuild,
         path : &Path
-    );
+    ) -> Result<(), LoggerError>;
 }
\ No newline at end of file
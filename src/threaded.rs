@@ -0,0 +1,284 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use std::collections::BTreeMap;
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use graph_process_manager_core::process::config::AbstractProcessConfiguration;
+use graph_process_manager_core::process::event::ExplorationEvent;
+use graph_process_manager_core::process::manager::GenericProcessManager;
+
+use crate::control::{LoggerControl, TerminationReason};
+use crate::dispatch::LoggersDispatcher;
+use crate::logger::AbstractProcessLogger;
+use crate::meta::{EventClock, EventStamp};
+use crate::registry::NodeRegistryPolicy;
+use crate::report::LoggersRunReport;
+
+
+/**
+ * A logger that can be moved to a worker thread.
+ **/
+pub type SendProcessLogger<Conf> = dyn AbstractProcessLogger<Conf> + Send;
+
+// a SendProcessLogger borrowed for the duration of the run
+type BorrowedSendLogger<'l, Conf> = dyn AbstractProcessLogger<Conf> + Send + 'l;
+
+
+/**
+ * What the exploration does when the channel towards a slow logger is full.
+ **/
+#[derive(Clone, Copy, PartialEq, Debug, Eq, Hash)]
+pub enum ThreadedBackPressure {
+    /** Wait for the logger to catch up, so that it receives every event. **/
+    Block,
+    /**
+     * Do not wait and drop the event for that logger.
+     * Dropped events are counted in LoggersRunReport::dropped_events.
+     * Only suitable for loggers that tolerate gaps in the event stream
+     * (e.g. a NewStep towards a node whose NewNode was dropped).
+     **/
+    DropEvents,
+}
+
+
+pub struct ThreadedDriverOptions {
+    /** Number of events that can be queued for each logger. **/
+    pub channel_capacity: usize,
+    pub back_pressure: ThreadedBackPressure,
+    /** Applies to the registry of each worker. **/
+    pub node_registry_policy: NodeRegistryPolicy,
+    /** Whether to catch panics in logger callbacks, as in DriveLoggersOptions. **/
+    pub supervised: bool,
+}
+
+impl ThreadedDriverOptions {
+    pub fn new(channel_capacity: usize, back_pressure: ThreadedBackPressure) -> Self {
+        Self {
+            channel_capacity,
+            back_pressure,
+            node_registry_policy: NodeRegistryPolicy::Automatic,
+            supervised: false,
+        }
+    }
+}
+
+impl Default for ThreadedDriverOptions {
+    fn default() -> Self {
+        Self::new(1024, ThreadedBackPressure::Block)
+    }
+}
+
+
+/**
+ * Variant of drive_loggers in which each logger runs on its own worker thread,
+ * so that slow loggers do not slow the exploration down.
+ *
+ * Events are sent to the workers over bounded channels (see ThreadedDriverOptions).
+ * Each worker keeps its own id→node registry, sharing the nodes with the exploration thread,
+ * and dispatches events with the same semantics as drive_loggers.
+ *
+ * log_initialize_process and log_terminate_process are called on the calling thread;
+ * all workers are joined before log_terminate_process is called.
+ *
+ * LoggerControl requests are honoured as soon as the exploration thread notices them,
+ * so a few more events may already have been sent to the other loggers.
+ * As in drive_loggers, StopAfterCurrentNode waits for the node that was being expanded
+ * when it was requested to be complete.
+ **/
+#[must_use]
+pub fn drive_loggers_threaded<Conf>(
+    manager: &mut GenericProcessManager<Conf>,
+    loggers: &mut [Box<SendProcessLogger<Conf>>],
+    options: &ThreadedDriverOptions,
+) -> LoggersRunReport
+where
+    Conf: 'static + AbstractProcessConfiguration,
+    Conf::ContextAndParameterization: Clone + Sync,
+    Conf::DomainSpecificNode: Send + Sync,
+    Conf::DomainSpecificStep: Send + Sync,
+    Conf::FiltrationResult: Send + Sync,
+{
+    // one dispatcher per logger, so that each of them can be moved to its own worker
+    let mut dispatchers: Vec<LoggersDispatcher<Conf, BorrowedSendLogger<'_, Conf>>> = loggers
+        .iter_mut()
+        .enumerate()
+        .map(|(logger_index, logger)| {
+            let borrowed: Box<BorrowedSendLogger<'_, Conf>> = Box::new(logger.as_mut());
+            LoggersDispatcher::new_with_offset(vec![borrowed], logger_index, options.node_registry_policy)
+                .with_supervision(options.supervised)
+        })
+        .collect();
+    for dispatcher in dispatchers.iter_mut() {
+        dispatcher.initialize(manager);
+    }
+
+    // the exploration thread needs the manager mutably, hence the workers get a copy of the context
+    let ctx = manager.context_and_param.clone();
+    let stop_request = Mutex::new(SharedStopRequest {
+        request: strongest_request(&dispatchers),
+        ..Default::default()
+    });
+    let mut dropped_events: BTreeMap<usize, u64> = BTreeMap::new();
+    // events are stamped when emitted rather than when each worker gets to them
+    let mut clock = EventClock::new();
+
+    let dispatchers = thread::scope(|scope| {
+        let mut senders: Vec<SyncSender<(EventStamp, Arc<ExplorationEvent<Conf>>)>> = vec![];
+        let mut workers = Vec::with_capacity(dispatchers.len());
+        for dispatcher in dispatchers {
+            let (sender, receiver) = sync_channel(options.channel_capacity);
+            senders.push(sender);
+            let (ctx, stop_request) = (&ctx, &stop_request);
+            workers.push(scope.spawn(move || run_worker(dispatcher, receiver, ctx, stop_request)));
+        }
+
+        // for StopAfterCurrentNode, the node after which to stop (or any node, if it is unknown)
+        let mut stop_after_node: Option<Option<u32>> = None;
+        loop {
+            {
+                let shared = stop_request.lock().unwrap();
+                match shared.request {
+                    Some((_, LoggerControl::Stop)) => break,
+                    // the workers lag behind, hence the node may already be complete
+                    Some((_, LoggerControl::StopAfterCurrentNode)) if shared.stop_node_completed => break,
+                    Some((_, LoggerControl::StopAfterCurrentNode)) => stop_after_node = Some(shared.stop_node),
+                    _ => {}
+                }
+            }
+            let Some(event) = manager.next() else { break };
+            let completed_node = match &event {
+                ExplorationEvent::AllChildrenProcessed { parent_node_id } => Some(*parent_node_id),
+                ExplorationEvent::NodeWithoutChildren { node_id } => Some(*node_id),
+                _ => None,
+            };
+            let stamp = clock.tick();
+            let event = Arc::new(event);
+            for (logger_index, sender) in senders.iter().enumerate() {
+                // sending only fails if the worker panicked, which is propagated when joining it
+                match options.back_pressure {
+                    ThreadedBackPressure::Block => {
+                        let _ = sender.send((stamp, Arc::clone(&event)));
+                    }
+                    ThreadedBackPressure::DropEvents => {
+                        if let Err(TrySendError::Full(_)) = sender.try_send((stamp, Arc::clone(&event))) {
+                            *dropped_events.entry(logger_index).or_default() += 1;
+                        }
+                    }
+                }
+            }
+            if let (Some(stop_node), Some(completed_node)) = (stop_after_node, completed_node) {
+                if stop_node.is_none_or(|stop_node| stop_node == completed_node) {
+                    break;
+                }
+            }
+        }
+        drop(senders);
+
+        workers
+            .into_iter()
+            .map(|worker| worker.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+            .collect::<Vec<_>>()
+    });
+
+    let reason = match stop_request.lock().unwrap().request {
+        None => TerminationReason::ExplorationCompleted,
+        Some((logger_index, _)) => dispatchers
+            .iter()
+            .find(|dispatcher| dispatcher.stop_request().is_some_and(|(index, _)| index == logger_index))
+            .map(|dispatcher| dispatcher.termination_reason())
+            .unwrap_or_default(),
+    };
+    let mut report = LoggersRunReport::default();
+    for dispatcher in dispatchers {
+        report.absorb(dispatcher.terminate_with(manager, reason.clone()));
+    }
+    report.termination = reason;
+    report.dropped_events = dropped_events;
+    report
+}
+
+
+fn run_worker<'l, Conf>(
+    mut dispatcher: LoggersDispatcher<Conf, BorrowedSendLogger<'l, Conf>>,
+    receiver: Receiver<(EventStamp, Arc<ExplorationEvent<Conf>>)>,
+    ctx: &Conf::ContextAndParameterization,
+    stop_request: &Mutex<SharedStopRequest>,
+) -> LoggersDispatcher<Conf, BorrowedSendLogger<'l, Conf>>
+where
+    Conf: AbstractProcessConfiguration,
+{
+    // keeps draining the channel after a stop so that the exploration thread never blocks on it
+    for (stamp, event) in receiver {
+        if dispatcher.wants_more_events() {
+            dispatcher.handle_stamped_event(ctx, &event, stamp);
+            if let Some(request) = dispatcher.stop_request() {
+                // a dispatcher that has stopped because of its own request has seen the node complete
+                let completed = !dispatcher.wants_more_events();
+                stop_request.lock().unwrap().merge(request, dispatcher.stop_node(), completed);
+            }
+        }
+    }
+    dispatcher
+}
+
+
+fn strongest_request<Conf, L>(dispatchers: &[LoggersDispatcher<Conf, L>]) -> Option<(usize, LoggerControl)>
+where
+    Conf: AbstractProcessConfiguration,
+    L: AbstractProcessLogger<Conf> + ?Sized,
+{
+    let mut strongest = None;
+    for request in dispatchers.iter().filter_map(|dispatcher| dispatcher.stop_request()) {
+        merge_request(&mut strongest, request);
+    }
+    strongest
+}
+
+/** The strongest stop request of the workers, shared with the exploration thread. **/
+#[derive(Default)]
+struct SharedStopRequest {
+    request: Option<(usize, LoggerControl)>,
+    // node that was being expanded when the request was made, as seen by the worker that made it
+    stop_node: Option<u32>,
+    // whether that worker has since seen the node complete
+    stop_node_completed: bool,
+}
+
+impl SharedStopRequest {
+    fn merge(&mut self, request: (usize, LoggerControl), stop_node: Option<u32>, completed: bool) {
+        if self.request == Some(request) {
+            self.stop_node_completed |= completed;
+            return;
+        }
+        merge_request(&mut self.request, request);
+        if self.request == Some(request) {
+            self.stop_node = stop_node;
+            self.stop_node_completed = completed;
+        }
+    }
+}
+
+fn merge_request(current: &mut Option<(usize, LoggerControl)>, request: (usize, LoggerControl)) {
+    let replace = match *current {
+        None => true,
+        Some((index, control)) => control < request.1 || (control == request.1 && request.0 < index),
+    };
+    if replace {
+        *current = Some(request);
+    }
+}
@@ -0,0 +1,101 @@
+/*
+Copyright 2020 Erwan Mahe (github.com/erwanM974)
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::any::Any;
+use std::fs;
+use std::io::ErrorKind;
+
+use crate::error::LoggerError;
+
+
+/**
+ * Empties a logger output folder, creating it if it does not exist yet.
+ **/
+pub(crate) fn reset_folder(folder: &str) -> Result<(), LoggerError> {
+    match fs::remove_dir_all(folder) {
+        Ok(()) => {}
+        Err(e) if e.kind() == ErrorKind::NotFound => {}
+        Err(e) => return Err(LoggerError::io(format!("removing folder '{}'", folder), e)),
+    }
+    create_folder(folder)
+}
+
+/**
+ * Creates a logger output folder and its parents if they do not exist yet.
+ * An empty path stands for the working directory, which is left as is.
+ **/
+pub(crate) fn create_folder(folder: &str) -> Result<(), LoggerError> {
+    if folder.is_empty() {
+        return Ok(());
+    }
+    fs::create_dir_all(folder)
+        .map_err(|e| LoggerError::io(format!("creating folder '{}'", folder), e))
+}
+
+
+/**
+ * Interprets the result of a call to the dot binary.
+ **/
+#[cfg(any(feature = "graphviz", feature = "nfait"))]
+pub(crate) fn check_dot_output(
+    output_file_name: &str,
+    output: std::io::Result<std::process::Output>,
+) -> Result<(), LoggerError> {
+    let context = format!("rendering '{}' with dot", output_file_name);
+    let output = output.map_err(|e| LoggerError::io(context.clone(), e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(LoggerError::render(
+            context,
+            format!("{} : {}", output.status, String::from_utf8_lossy(&output.stderr).trim()),
+        ))
+    }
+}
+
+
+/**
+ * Message carried by the payload of a panic, as given by panic! or unwrap().
+ **/
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "panic with a non-string payload".to_string()
+    }
+}
+
+
+/**
+ * Escapes a text so that it can be used as XML character data or attribute value.
+ **/
+#[cfg(feature = "graphml")]
+pub(crate) fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&'  => escaped.push_str("&amp;"),
+            '<'  => escaped.push_str("&lt;"),
+            '>'  => escaped.push_str("&gt;"),
+            '"'  => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c    => escaped.push(c),
+        }
+    }
+    escaped
+}
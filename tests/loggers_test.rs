@@ -35,17 +35,32 @@ use std::cell::RefCell;
 use std::collections::HashSet;
 use std::path::Path;
 use std::rc::Rc;
+use std::sync::{mpsc, Arc, Mutex};
 
 use graph_process_manager_core::process::config::AbstractProcessConfiguration;
+use graph_process_manager_core::process::event::ExplorationEvent;
 use graph_process_manager_core::process::filter::{AbstractStepFilter, GenericFiltersManager};
 use graph_process_manager_core::process::manager::GenericProcessManager;
 use graph_process_manager_core::process::persistent_state::AbstractProcessMutablePersistentState;
 use graph_process_manager_core::queue::priorities::{AbstractPriorities, GenericProcessPriorities};
 use graph_process_manager_core::queue::strategy::QueueSearchStrategy;
 
-use graph_process_manager_loggers::logger::{AbstractProcessLogger, drive_loggers};
+use graph_process_manager_loggers::control::{LoggerControl, TerminationReason};
+use graph_process_manager_loggers::driver::LoggerDriver;
+use graph_process_manager_loggers::error::LoggerError;
+use graph_process_manager_loggers::logger::{AbstractProcessLogger, LoggerResult, drive_loggers, drive_loggers_with_options, DriveLoggersOptions};
+use graph_process_manager_loggers::logger_registry::LoggerRegistry;
+use graph_process_manager_loggers::meta::EventMeta;
+use graph_process_manager_loggers::registry::NodeRegistryPolicy;
+use graph_process_manager_loggers::report::LoggerEventKind;
+use graph_process_manager_loggers::threaded::{drive_loggers_threaded, SendProcessLogger, ThreadedBackPressure, ThreadedDriverOptions};
 use graph_process_manager_loggers::nodesprint::logger::GenericNodesPrintLogger;
 use graph_process_manager_loggers::nodesprint::printer::NodesPrintProcessPrinter;
+use graph_process_manager_loggers::record::codec::ExplorationEventCodec;
+use graph_process_manager_loggers::record::logger::GenericEventRecorderLogger;
+use graph_process_manager_loggers::record::replay::replay_loggers;
+use graph_process_manager_loggers::statistics::logger::GenericStatisticsLogger;
+use graph_process_manager_loggers::statistics::summary::ExplorationStatistics;
 use graph_process_manager_loggers::stepstrace::logger::GenericStepsTraceLogger;
 use graph_process_manager_loggers::stepstrace::object::ObjectToBuildWhenTracingSteps;
 use graph_process_manager_loggers::stepstrace::printer::StepsTraceProcessPrinter;
@@ -128,6 +143,7 @@ fn make_manager_with_step_filter(
 struct RecordingState {
     initialized:            bool,
     terminated:             bool,
+    termination:            Option<TerminationReason>,
     new_nodes:              Vec<(u32, u8)>,          // (id, node_value)
     new_steps:              Vec<(u32, u8, u32, u8)>, // (origin_id, step_val, target_id, target_node_val)
     all_children_processed: Vec<u32>,                // parent_id
@@ -145,26 +161,35 @@ impl RecordingLogger {
 }
 
 impl AbstractProcessLogger<TestConf> for RecordingLogger {
-    fn log_initialize_process(&mut self, _m: &GenericProcessManager<TestConf>) {
+    fn log_initialize_process(&mut self, _m: &GenericProcessManager<TestConf>) -> LoggerResult {
         self.0.borrow_mut().initialized = true;
+        Ok(LoggerControl::Continue)
     }
-    fn log_new_node(&mut self, _ctx: &(), id: u32, node: &Node) {
+    fn log_new_node(&mut self, _ctx: &(), _meta: &EventMeta<TestConf>, id: u32, node: &Node) -> LoggerResult {
         self.0.borrow_mut().new_nodes.push((id, node.0));
+        Ok(LoggerControl::Continue)
     }
-    fn log_new_step(&mut self, _ctx: &(), origin: u32, step: &Step, target_id: u32, target: &Node) {
+    fn log_new_step(&mut self, _ctx: &(), _meta: &EventMeta<TestConf>, origin: u32, step: &Step, target_id: u32, target: &Node) -> LoggerResult {
         self.0.borrow_mut().new_steps.push((origin, step.0, target_id, target.0));
+        Ok(LoggerControl::Continue)
     }
-    fn log_all_children_processed(&mut self, _ctx: &(), parent_id: u32) {
+    fn log_all_children_processed(&mut self, _ctx: &(), _meta: &EventMeta<TestConf>, parent_id: u32) -> LoggerResult {
         self.0.borrow_mut().all_children_processed.push(parent_id);
+        Ok(LoggerControl::Continue)
     }
-    fn log_notify_node_without_children(&mut self, _ctx: &(), node_id: u32) {
+    fn log_notify_node_without_children(&mut self, _ctx: &(), _meta: &EventMeta<TestConf>, node_id: u32) -> LoggerResult {
         self.0.borrow_mut().nodes_without_children.push(node_id);
+        Ok(LoggerControl::Continue)
     }
-    fn log_filtered(&mut self, _ctx: &(), parent_id: u32, _result: &()) {
+    fn log_filtered(&mut self, _ctx: &(), _meta: &EventMeta<TestConf>, parent_id: u32, _result: &()) -> LoggerResult {
         self.0.borrow_mut().filtrations.push(parent_id);
+        Ok(LoggerControl::Continue)
     }
-    fn log_terminate_process(&mut self, _m: &GenericProcessManager<TestConf>) {
-        self.0.borrow_mut().terminated = true;
+    fn log_terminate_process(&mut self, _m: &GenericProcessManager<TestConf>, reason: &TerminationReason) -> LoggerResult {
+        let mut s = self.0.borrow_mut();
+        s.terminated = true;
+        s.termination = Some(reason.clone());
+        Ok(LoggerControl::Continue)
     }
 }
 
@@ -172,7 +197,7 @@ fn run_recording(memoized: bool) -> Rc<RefCell<RecordingState>> {
     let (logger, state) = RecordingLogger::new();
     let mut manager = make_manager(memoized);
     let mut loggers: Vec<Box<dyn AbstractProcessLogger<TestConf>>> = vec![Box::new(logger)];
-    drive_loggers(&mut manager, &mut loggers);
+    assert!(drive_loggers(&mut manager, &mut loggers).is_success());
     state
 }
 
@@ -182,7 +207,7 @@ fn run_recording_with_filter(
     let (logger, state) = RecordingLogger::new();
     let mut manager = make_manager_with_step_filter(filter);
     let mut loggers: Vec<Box<dyn AbstractProcessLogger<TestConf>>> = vec![Box::new(logger)];
-    drive_loggers(&mut manager, &mut loggers);
+    assert!(drive_loggers(&mut manager, &mut loggers).is_success());
     state
 }
 
@@ -256,6 +281,99 @@ fn memoized_back_edge_step_dispatched_with_correct_target_node() {
     }
 }
 
+// === Tests: node registry policies ============================================
+
+fn run_recording_with_policy(memoized: bool, policy: NodeRegistryPolicy) -> Rc<RefCell<RecordingState>> {
+    let (logger, state) = RecordingLogger::new();
+    let mut manager = make_manager(memoized);
+    let mut loggers: Vec<Box<dyn AbstractProcessLogger<TestConf>>> = vec![Box::new(logger)];
+    let report = drive_loggers_with_options(&mut manager, &mut loggers, &DriveLoggersOptions::new(policy));
+    assert!(report.is_success());
+    state
+}
+
+#[test]
+fn no_memo_bounded_registries_lose_no_step() {
+    let reference = run_recording(false);
+    for policy in [
+        NodeRegistryPolicy::DropCompletedNodes,
+        NodeRegistryPolicy::LeastRecentlyUsed(1),
+        NodeRegistryPolicy::DepthWindow(0),
+    ] {
+        let state = run_recording_with_policy(false, policy);
+        assert_eq!(state.borrow().new_steps, reference.borrow().new_steps, "{:?}", policy);
+    }
+}
+
+#[test]
+fn memo_drop_completed_nodes_skips_back_edges_to_dropped_nodes() {
+    // node 1 is complete before the back-edge 2→1 is explored
+    let state = run_recording_with_policy(true, NodeRegistryPolicy::DropCompletedNodes);
+    let s = state.borrow();
+    assert_eq!(s.new_steps.len(), 2);
+    assert!(s.new_steps.iter().all(|&(_, step_val, _, target_val)| step_val == target_val));
+}
+
+// === Tests: event metadata ===================================================
+
+type NodeMetaRecord = (u32, Option<u32>, Option<u32>, Option<u32>); // (id, depth, parent_id, discovery_index)
+
+#[derive(Default)]
+struct MetaState {
+    event_indices: Vec<u64>,
+    nodes:         Vec<NodeMetaRecord>,
+    steps:         Vec<(u32, Option<u8>, Option<u32>)>,               // (origin_id, origin_node_val, target_depth)
+}
+
+struct MetaLogger(Rc<RefCell<MetaState>>);
+
+impl AbstractProcessLogger<TestConf> for MetaLogger {
+    fn log_new_node(&mut self, _ctx: &(), meta: &EventMeta<TestConf>, id: u32, _node: &Node) -> LoggerResult {
+        let mut s = self.0.borrow_mut();
+        s.event_indices.push(meta.event_index);
+        s.nodes.push((id, meta.depth, meta.parent_node_id, meta.discovery_index));
+        Ok(LoggerControl::Continue)
+    }
+    fn log_new_step(&mut self, _ctx: &(), meta: &EventMeta<TestConf>, origin: u32, _step: &Step, _target_id: u32, _target: &Node) -> LoggerResult {
+        let mut s = self.0.borrow_mut();
+        s.event_indices.push(meta.event_index);
+        s.steps.push((origin, meta.origin_node.map(|n| n.0), meta.depth));
+        Ok(LoggerControl::Continue)
+    }
+    fn log_all_children_processed(&mut self, _ctx: &(), meta: &EventMeta<TestConf>, _parent_id: u32) -> LoggerResult {
+        self.0.borrow_mut().event_indices.push(meta.event_index);
+        Ok(LoggerControl::Continue)
+    }
+    fn log_notify_node_without_children(&mut self, _ctx: &(), meta: &EventMeta<TestConf>, _node_id: u32) -> LoggerResult {
+        self.0.borrow_mut().event_indices.push(meta.event_index);
+        Ok(LoggerControl::Continue)
+    }
+}
+
+#[test]
+fn event_meta_gives_depth_parent_and_discovery_index() {
+    let state = Rc::new(RefCell::new(MetaState::default()));
+    let mut manager = make_manager(false);
+    let mut loggers: Vec<Box<dyn AbstractProcessLogger<TestConf>>> = vec![Box::new(MetaLogger(Rc::clone(&state)))];
+    assert!(drive_loggers(&mut manager, &mut loggers).is_success());
+    let s = state.borrow();
+
+    // node values 0, 2, 1, 1 : the second copy of node 1 is discovered from node 2
+    let (root, two, one, one_again) = (s.nodes[0], s.nodes[1], s.nodes[2], s.nodes[3]);
+    assert_eq!((root.1, root.2, root.3), (Some(0), None, Some(0)));
+    assert_eq!((two.1, two.2, two.3), (Some(1), Some(root.0), Some(1)));
+    assert_eq!((one.1, one.2, one.3), (Some(1), Some(root.0), Some(2)));
+    assert_eq!((one_again.1, one_again.2, one_again.3), (Some(2), Some(two.0), Some(3)));
+
+    let origin_values: Vec<Option<u8>> = s.steps.iter().map(|&(_, origin_val, _)| origin_val).collect();
+    assert_eq!(origin_values, vec![Some(0), Some(0), Some(2)]);
+    assert_eq!(s.steps[2].2, Some(2));
+
+    // every exploration event is dispatched once, in the order of emission
+    let expected: Vec<u64> = (0..s.event_indices.len() as u64).collect();
+    assert_eq!(s.event_indices, expected);
+}
+
 // === Tests: structural events =================================================
 
 #[test]
@@ -292,7 +410,7 @@ fn all_children_processed_comes_after_all_steps_from_that_parent() {
         let (logger, st) = RecordingLogger::new();
         let mut manager = make_manager(false);
         let mut loggers: Vec<Box<dyn AbstractProcessLogger<TestConf>>> = vec![Box::new(logger)];
-        drive_loggers(&mut manager, &mut loggers);
+        assert!(drive_loggers(&mut manager, &mut loggers).is_success());
         let b = st.borrow();
         b.all_children_processed.iter().map(|id| id.to_string()).collect()
     };
@@ -339,7 +457,7 @@ fn multiple_loggers_receive_identical_events() {
     let mut manager = make_manager(false);
     let mut loggers: Vec<Box<dyn AbstractProcessLogger<TestConf>>> =
         vec![Box::new(logger_a), Box::new(logger_b)];
-    drive_loggers(&mut manager, &mut loggers);
+    assert!(drive_loggers(&mut manager, &mut loggers).is_success());
 
     let a = state_a.borrow();
     let b = state_b.borrow();
@@ -352,14 +470,507 @@ fn multiple_loggers_receive_identical_events() {
     assert_eq!(a.terminated, b.terminated);
 }
 
+#[test]
+fn logger_registry_keeps_indices_of_loggers_after_one_is_taken_back() {
+    let mut manager = make_manager(false);
+    let mut loggers = LoggerRegistry::new();
+    let recorder = loggers.add(RecordingLogger::new().0);
+    let failing = loggers.add(FailOnNode(2));
+    assert!(loggers.take(recorder).is_some());
+    assert!(loggers.take(recorder).is_none());
+
+    let report = loggers.drive(&mut manager);
+    assert_eq!(report.failures.len(), 1);
+    assert_eq!(report.failures[0].logger_index, failing.get_index());
+    assert_eq!(loggers.get(failing).map(|logger| logger.0), Some(2));
+}
+
+// === Tests: step-by-step driver ===============================================
+
+#[test]
+fn step_by_step_driver_delivers_the_same_events_as_drive_loggers() {
+    let reference = run_recording(false);
+    let (logger, state) = RecordingLogger::new();
+    let mut manager = make_manager(false);
+    let mut driver = LoggerDriver::new(&mut manager, vec![Box::new(logger)]);
+    assert!(state.borrow().initialized);
+
+    assert_eq!(driver.run_n(3), 3);
+    assert_eq!(driver.get_event_count(), 3);
+    driver.run_until(|event| matches!(event, ExplorationEvent::AllChildrenProcessed { .. }));
+    assert_eq!(state.borrow().all_children_processed.len(), 1);
+    assert!(!state.borrow().terminated);
+
+    assert!(driver.finish().is_success());
+    let (s, r) = (state.borrow(), reference.borrow());
+    assert_eq!(s.new_nodes, r.new_nodes);
+    assert_eq!(s.new_steps, r.new_steps);
+    assert_eq!(s.all_children_processed, r.all_children_processed);
+    assert_eq!(s.nodes_without_children, r.nodes_without_children);
+    assert!(s.terminated);
+}
+
+#[test]
+fn step_by_step_driver_adds_and_removes_loggers_mid_run() {
+    let (first, first_state) = RecordingLogger::new();
+    let (late, late_state) = RecordingLogger::new();
+    let mut manager = make_manager(false);
+    let mut driver = LoggerDriver::new(&mut manager, vec![Box::new(first)]);
+    driver.run_until(|event| matches!(event, ExplorationEvent::AllChildrenProcessed { .. }));
+
+    assert_eq!(driver.add_logger(Box::new(late)), 1);
+    assert!(driver.remove_logger(0).is_some());
+    assert!(driver.remove_logger(0).is_none());
+    assert!(driver.finish().is_success());
+
+    let (first_s, late_s) = (first_state.borrow(), late_state.borrow());
+    assert!(!first_s.terminated, "a removed logger is not terminated");
+    assert!(late_s.initialized && late_s.terminated);
+    assert_eq!(first_s.new_nodes.len() + late_s.new_nodes.len(), 4);
+    assert_eq!(late_s.all_children_processed.len(), 1);
+}
+
+// === Tests: logger failures ==================================================
+
+/// Fails when asked to log the node with the given id.
+struct FailOnNode(u32);
+
+impl AbstractProcessLogger<TestConf> for FailOnNode {
+    fn get_logger_name(&self) -> String {
+        "fail_on_node".to_string()
+    }
+    fn log_new_node(&mut self, _ctx: &(), _meta: &EventMeta<TestConf>, id: u32, _node: &Node) -> LoggerResult {
+        if id == self.0 {
+            Err(LoggerError::Custom("disk full".to_string()))
+        } else {
+            Ok(LoggerControl::Continue)
+        }
+    }
+}
+
+#[test]
+fn failing_logger_is_reported_with_index_event_and_cause() {
+    let (recorder, state) = RecordingLogger::new();
+    let mut manager = make_manager(false);
+    let mut loggers: Vec<Box<dyn AbstractProcessLogger<TestConf>>> =
+        vec![Box::new(recorder), Box::new(FailOnNode(2))];
+    let report = drive_loggers(&mut manager, &mut loggers);
+
+    assert!(!report.is_success());
+    assert_eq!(report.failures.len(), 1, "a failed logger is disabled, so it fails only once");
+    let failure = &report.failures[0];
+    assert_eq!(failure.logger_index, 1);
+    assert_eq!(failure.logger_name, "fail_on_node");
+    assert_eq!(failure.event, LoggerEventKind::NewNode { node_id: 2 });
+    assert!(failure.error.to_string().contains("disk full"));
+
+    // the other logger is unaffected
+    let s = state.borrow();
+    assert_eq!(s.new_nodes.len(), 4);
+    assert!(s.terminated);
+}
+
+#[test]
+fn nodesprint_reports_io_error_when_folder_cannot_be_created() {
+    let blocker = std::env::temp_dir().join("gpm_test_nodesprint_blocker");
+    let _ = std::fs::remove_dir_all(&blocker);
+    std::fs::write(&blocker, "not a folder").unwrap();
+    let out = blocker.join("sub");
+    let mut manager = make_manager(false);
+    let mut loggers: Vec<Box<dyn AbstractProcessLogger<TestConf>>> = vec![
+        Box::new(GenericNodesPrintLogger::new(
+            Box::new(PlainTextNodePrinter),
+            "n".to_string(), "txt".to_string(),
+            out.to_str().unwrap().to_string(),
+        )),
+    ];
+    let report = drive_loggers(&mut manager, &mut loggers);
+    assert_eq!(report.failures.len(), 1);
+    assert_eq!(report.failures[0].event, LoggerEventKind::InitializeProcess);
+    assert!(matches!(report.failures[0].error, LoggerError::Io { .. }));
+    assert!(report.into_result().is_err());
+}
+
+/// Panics when asked to log the node with the given id.
+struct PanicOnNode(u32);
+
+impl AbstractProcessLogger<TestConf> for PanicOnNode {
+    fn log_new_node(&mut self, _ctx: &(), _meta: &EventMeta<TestConf>, id: u32, _node: &Node) -> LoggerResult {
+        if id == self.0 {
+            panic!("no trace for node {}", id);
+        }
+        Ok(LoggerControl::Continue)
+    }
+}
+
+#[test]
+fn supervised_mode_disables_a_panicking_logger_and_reports_the_panic() {
+    let (recorder, state) = RecordingLogger::new();
+    let mut manager = make_manager(false);
+    let mut loggers: Vec<Box<dyn AbstractProcessLogger<TestConf>>> =
+        vec![Box::new(PanicOnNode(2)), Box::new(recorder)];
+    let options = DriveLoggersOptions { supervised: true, ..Default::default() };
+    let report = drive_loggers_with_options(&mut manager, &mut loggers, &options);
+
+    assert!(!report.is_success());
+    assert!(report.failures.is_empty());
+    assert_eq!(report.panics.len(), 1);
+    assert_eq!(report.panics[0].logger_index, 0);
+    assert_eq!(report.panics[0].event, LoggerEventKind::NewNode { node_id: 2 });
+    assert_eq!(report.panics[0].message, "no trace for node 2");
+
+    let s = state.borrow();
+    assert_eq!(s.new_nodes.len(), 4);
+    assert!(s.terminated);
+}
+
+// === Tests: early termination ================================================
+
+/// Returns the given control when asked to log the node with the given id.
+struct ControlOnNode(u32, LoggerControl);
+
+impl AbstractProcessLogger<TestConf> for ControlOnNode {
+    fn log_new_node(&mut self, _ctx: &(), _meta: &EventMeta<TestConf>, id: u32, _node: &Node) -> LoggerResult {
+        if id == self.0 { Ok(self.1) } else { Ok(LoggerControl::Continue) }
+    }
+}
+
+#[test]
+fn logger_stop_ends_exploration_and_still_terminates_every_logger() {
+    let (recorder, state) = RecordingLogger::new();
+    let mut manager = make_manager(false);
+    let mut loggers: Vec<Box<dyn AbstractProcessLogger<TestConf>>> =
+        vec![Box::new(recorder), Box::new(ControlOnNode(2, LoggerControl::Stop))];
+    let report = drive_loggers(&mut manager, &mut loggers);
+    assert!(report.is_success(), "stopping early is not a failure");
+
+    let s = state.borrow();
+    assert_eq!(s.new_nodes.iter().map(|&(id, _)| id).collect::<Vec<u32>>(), vec![1, 2]);
+    assert!(s.new_steps.is_empty(), "no event is dispatched after the stopping one");
+    assert!(s.terminated);
+    let expected = TerminationReason::StoppedByLogger {
+        logger_index: 1,
+        logger_name: std::any::type_name::<ControlOnNode>().to_string(),
+        control: LoggerControl::Stop,
+    };
+    assert_eq!(s.termination, Some(expected.clone()));
+    assert_eq!(report.termination, expected);
+}
+
+#[test]
+fn logger_stop_after_current_node_finishes_the_expanded_node() {
+    // node 2 is discovered while expanding the root (id=1):
+    // the remaining child of the root is still processed, but not node 2's children
+    let (recorder, state) = RecordingLogger::new();
+    let mut manager = make_manager(false);
+    let mut loggers: Vec<Box<dyn AbstractProcessLogger<TestConf>>> =
+        vec![Box::new(recorder), Box::new(ControlOnNode(2, LoggerControl::StopAfterCurrentNode))];
+    assert!(drive_loggers(&mut manager, &mut loggers).is_success());
+
+    let s = state.borrow();
+    assert_eq!(s.new_nodes.len(), 3);
+    assert_eq!(s.new_steps.len(), 3 - 1, "every logged node but the root has its incoming step");
+    assert_eq!(s.all_children_processed, vec![1]);
+    assert!(matches!(s.termination,
+        Some(TerminationReason::StoppedByLogger { control: LoggerControl::StopAfterCurrentNode, .. })));
+}
+
+#[test]
+fn logger_stop_after_current_node_on_a_leaf_finishes_its_parent() {
+    // node 4 is a leaf discovered while expanding node 2:
+    // the run ends once node 2 is complete, not as soon as the leaf is
+    let (recorder, state) = RecordingLogger::new();
+    let mut manager = make_manager(false);
+    let mut loggers: Vec<Box<dyn AbstractProcessLogger<TestConf>>> =
+        vec![Box::new(recorder), Box::new(ControlOnNode(4, LoggerControl::StopAfterCurrentNode))];
+    assert!(drive_loggers(&mut manager, &mut loggers).is_success());
+
+    let s = state.borrow();
+    assert_eq!(s.new_nodes.len(), 4);
+    assert_eq!(s.nodes_without_children, vec![3, 4]);
+    assert_eq!(s.all_children_processed, vec![1, 2]);
+}
+
+/// Asks to stop after the current node when notified that the node with the given id has no children.
+struct StopAfterLeaf(u32);
+
+impl AbstractProcessLogger<TestConf> for StopAfterLeaf {
+    fn log_notify_node_without_children(&mut self, _ctx: &(), _meta: &EventMeta<TestConf>, node_id: u32) -> LoggerResult {
+        if node_id == self.0 { Ok(LoggerControl::StopAfterCurrentNode) } else { Ok(LoggerControl::Continue) }
+    }
+}
+
+#[test]
+fn logger_stop_after_current_node_on_a_leaf_notification_stops_at_that_leaf() {
+    // leaf 3 is notified while its parent (id=1) is still being expanded:
+    // the run ends right after the leaf rather than after the root
+    let (recorder, state) = RecordingLogger::new();
+    let mut manager = make_manager(false);
+    let mut loggers: Vec<Box<dyn AbstractProcessLogger<TestConf>>> =
+        vec![Box::new(recorder), Box::new(StopAfterLeaf(3))];
+    assert!(drive_loggers(&mut manager, &mut loggers).is_success());
+
+    let s = state.borrow();
+    assert_eq!(s.nodes_without_children, vec![3]);
+    assert!(s.all_children_processed.is_empty());
+    assert!(matches!(s.termination,
+        Some(TerminationReason::StoppedByLogger { control: LoggerControl::StopAfterCurrentNode, .. })));
+}
+
+#[test]
+fn completed_exploration_is_reported_as_such() {
+    let state = run_recording(true);
+    assert_eq!(state.borrow().termination, Some(TerminationReason::ExplorationCompleted));
+}
+
+// === Tests: record and replay ================================================
+
+struct TestCodec;
+
+impl ExplorationEventCodec<TestConf> for TestCodec {
+    fn encode_node(&self, node: &Node, buffer: &mut Vec<u8>) { buffer.push(node.0) }
+    fn decode_node(&self, bytes: &[u8]) -> Result<Node, LoggerError> { Ok(Node(bytes[0])) }
+    fn encode_step(&self, step: &Step, buffer: &mut Vec<u8>) { buffer.push(step.0) }
+    fn decode_step(&self, bytes: &[u8]) -> Result<Step, LoggerError> { Ok(Step(bytes[0])) }
+    fn encode_filtration_result(&self, _result: &(), _buffer: &mut Vec<u8>) {}
+    fn decode_filtration_result(&self, _bytes: &[u8]) -> Result<(), LoggerError> { Ok(()) }
+}
+
+fn assert_same_events(a: &RecordingState, b: &RecordingState) {
+    assert_eq!(a.new_nodes, b.new_nodes);
+    assert_eq!(a.new_steps, b.new_steps);
+    assert_eq!(a.all_children_processed, b.all_children_processed);
+    assert_eq!(a.nodes_without_children, b.nodes_without_children);
+    assert_eq!(a.filtrations, b.filtrations);
+}
+
+#[test]
+fn replayed_recording_reproduces_the_event_stream() {
+    let out = std::env::temp_dir().join("gpm_test_record");
+    let recorder = GenericEventRecorderLogger::new(
+        TestCodec, out.to_str().unwrap().to_string(), "memo.rec".to_string());
+    let path = recorder.get_recording_path();
+    let (live, live_state) = RecordingLogger::new();
+    let mut manager = make_manager(true);
+    let mut loggers: Vec<Box<dyn AbstractProcessLogger<TestConf>>> = vec![Box::new(recorder), Box::new(live)];
+    assert!(drive_loggers(&mut manager, &mut loggers).is_success());
+
+    let (replayed, replayed_state) = RecordingLogger::new();
+    let mut loggers: Vec<Box<dyn AbstractProcessLogger<TestConf>>> = vec![Box::new(replayed)];
+    let report = replay_loggers(&make_manager(true), &path, &TestCodec, &mut loggers, &DriveLoggersOptions::default()).unwrap();
+    assert!(report.is_success());
+
+    let replayed = replayed_state.borrow();
+    assert!(replayed.initialized && replayed.terminated);
+    // the memoized back-edge 2→1 still gets its target node from the replay registry
+    assert_eq!(replayed.new_steps.len(), 3);
+    assert_same_events(&live_state.borrow(), &replayed);
+}
+
+#[test]
+fn replayed_recording_includes_filtrations() {
+    let out = std::env::temp_dir().join("gpm_test_record");
+    let recorder = GenericEventRecorderLogger::new(
+        TestCodec, out.to_str().unwrap().to_string(), "filtered.rec".to_string());
+    let path = recorder.get_recording_path();
+    let (live, live_state) = RecordingLogger::new();
+    let mut manager = make_manager_with_step_filter(Box::new(BlockStepTo(2)));
+    let mut loggers: Vec<Box<dyn AbstractProcessLogger<TestConf>>> = vec![Box::new(recorder), Box::new(live)];
+    assert!(drive_loggers(&mut manager, &mut loggers).is_success());
+
+    let (replayed, replayed_state) = RecordingLogger::new();
+    let mut loggers: Vec<Box<dyn AbstractProcessLogger<TestConf>>> = vec![Box::new(replayed)];
+    let report = replay_loggers(&make_manager(false), &path, &TestCodec, &mut loggers, &DriveLoggersOptions::default()).unwrap();
+    assert!(report.is_success());
+    assert_eq!(replayed_state.borrow().filtrations, vec![1u32]);
+    assert_same_events(&live_state.borrow(), &replayed_state.borrow());
+}
+
+#[test]
+fn replay_honours_the_registry_policy_and_supervision() {
+    let out = std::env::temp_dir().join("gpm_test_record");
+    let recorder = GenericEventRecorderLogger::new(
+        TestCodec, out.to_str().unwrap().to_string(), "options.rec".to_string());
+    let path = recorder.get_recording_path();
+    let mut manager = make_manager(true);
+    let mut loggers: Vec<Box<dyn AbstractProcessLogger<TestConf>>> = vec![Box::new(recorder)];
+    assert!(drive_loggers(&mut manager, &mut loggers).is_success());
+
+    let (replayed, replayed_state) = RecordingLogger::new();
+    let mut loggers: Vec<Box<dyn AbstractProcessLogger<TestConf>>> = vec![Box::new(PanicOnNode(2)), Box::new(replayed)];
+    let options = DriveLoggersOptions { node_registry_policy: NodeRegistryPolicy::DropCompletedNodes, supervised: true };
+    let report = replay_loggers(&make_manager(true), &path, &TestCodec, &mut loggers, &options).unwrap();
+    assert_eq!(report.panics.len(), 1);
+    assert_eq!(report.panics[0].event, LoggerEventKind::NewNode { node_id: 2 });
+
+    let replayed = replayed_state.borrow();
+    assert!(replayed.terminated);
+    // node 1 is complete before the back-edge 2→1 is replayed
+    assert_eq!(replayed.new_steps.len(), 2);
+}
+
+#[test]
+fn replay_rejects_a_file_that_is_not_a_recording() {
+    let path = std::env::temp_dir().join("gpm_test_not_a_recording.rec");
+    std::fs::write(&path, "hello world").unwrap();
+    let mut loggers: Vec<Box<dyn AbstractProcessLogger<TestConf>>> = vec![];
+    assert!(replay_loggers(&make_manager(false), &path, &TestCodec, &mut loggers, &DriveLoggersOptions::default()).is_err());
+}
+
+// === Tests: threaded driver ==================================================
+
+/// Send logger recording the kind of each received event, optionally slowed down,
+/// or held on its first event until the gate is opened.
+struct EventKindsLogger {
+    kinds: Arc<Mutex<Vec<String>>>,
+    delay: std::time::Duration,
+    gate: Option<mpsc::Receiver<()>>,
+}
+
+impl EventKindsLogger {
+    fn new(delay_ms: u64) -> (Self, Arc<Mutex<Vec<String>>>) {
+        let kinds = Arc::new(Mutex::new(vec![]));
+        (Self { kinds: Arc::clone(&kinds), delay: std::time::Duration::from_millis(delay_ms), gate: None }, kinds)
+    }
+    fn gated(gate: mpsc::Receiver<()>) -> (Self, Arc<Mutex<Vec<String>>>) {
+        let (logger, kinds) = Self::new(0);
+        (Self { gate: Some(gate), ..logger }, kinds)
+    }
+    fn push(&mut self, kind: String) -> LoggerResult {
+        if let Some(gate) = self.gate.take() {
+            let _ = gate.recv();
+        }
+        std::thread::sleep(self.delay);
+        self.kinds.lock().unwrap().push(kind);
+        Ok(LoggerControl::Continue)
+    }
+}
+
+impl AbstractProcessLogger<TestConf> for EventKindsLogger {
+    fn log_new_node(&mut self, _ctx: &(), _meta: &EventMeta<TestConf>, id: u32, _node: &Node) -> LoggerResult {
+        self.push(format!("node {}", id))
+    }
+    fn log_new_step(&mut self, _ctx: &(), _meta: &EventMeta<TestConf>, origin: u32, _step: &Step, target_id: u32, _target: &Node) -> LoggerResult {
+        self.push(format!("step {}->{}", origin, target_id))
+    }
+    fn log_all_children_processed(&mut self, _ctx: &(), _meta: &EventMeta<TestConf>, parent_id: u32) -> LoggerResult {
+        self.push(format!("done {}", parent_id))
+    }
+    fn log_notify_node_without_children(&mut self, _ctx: &(), _meta: &EventMeta<TestConf>, node_id: u32) -> LoggerResult {
+        self.push(format!("leaf {}", node_id))
+    }
+    fn log_terminate_process(&mut self, _m: &GenericProcessManager<TestConf>, _reason: &TerminationReason) -> LoggerResult {
+        self.push("terminate".to_string())
+    }
+}
+
+#[test]
+fn threaded_driver_delivers_the_same_events_as_drive_loggers() {
+    let (sequential, sequential_kinds) = EventKindsLogger::new(0);
+    let mut manager = make_manager(true);
+    let mut loggers: Vec<Box<dyn AbstractProcessLogger<TestConf>>> = vec![Box::new(sequential)];
+    assert!(drive_loggers(&mut manager, &mut loggers).is_success());
+
+    let (slow, slow_kinds) = EventKindsLogger::new(2);
+    let (fast, fast_kinds) = EventKindsLogger::new(0);
+    let mut manager = make_manager(true);
+    let mut loggers: Vec<Box<SendProcessLogger<TestConf>>> = vec![Box::new(slow), Box::new(fast)];
+    let report = drive_loggers_threaded(&mut manager, &mut loggers, &ThreadedDriverOptions::default());
+    assert!(report.is_success());
+    assert!(report.dropped_events.is_empty());
+
+    let expected = sequential_kinds.lock().unwrap().clone();
+    assert_eq!(expected.last().map(String::as_str), Some("terminate"));
+    assert_eq!(*slow_kinds.lock().unwrap(), expected, "workers are joined before termination");
+    assert_eq!(*fast_kinds.lock().unwrap(), expected);
+}
+
+/// Never filters, but opens the gate when the steps of the given node are checked, on the exploration thread.
+struct OpenGateOnExpansion(u8, mpsc::Sender<()>);
+
+impl AbstractStepFilter<TestConf> for OpenGateOnExpansion {
+    fn apply_step_filter(&self, _ctx: &(), _state: &(), parent: &Node, _step: &Step) -> Option<()> {
+        if parent.0 == self.0 {
+            let _ = self.1.send(());
+        }
+        None
+    }
+}
+
+#[test]
+fn threaded_driver_drops_and_counts_events_for_slow_loggers() {
+    // node 2 is expanded once 7 events have been sent, the logger being held on the first one:
+    // with room for a single event in the channel, at least 5 of them are dropped
+    let (open, gate) = mpsc::channel();
+    let (slow, slow_kinds) = EventKindsLogger::gated(gate);
+    let mut manager = make_manager_with_step_filter(Box::new(OpenGateOnExpansion(2, open)));
+    let mut loggers: Vec<Box<SendProcessLogger<TestConf>>> = vec![Box::new(slow)];
+    let options = ThreadedDriverOptions::new(1, ThreadedBackPressure::DropEvents);
+    let report = drive_loggers_threaded(&mut manager, &mut loggers, &options);
+
+    // 4 nodes, 3 steps, 2 nodes with children and 2 leaves
+    let received = slow_kinds.lock().unwrap().iter().filter(|k| *k != "terminate").count() as u64;
+    let dropped = report.dropped_events.get(&0).copied().unwrap_or(0);
+    assert_eq!(received + dropped, 11);
+    assert!(dropped >= 5);
+}
+
+#[test]
+fn threaded_driver_honours_stop_requests() {
+    let (recorder, kinds) = EventKindsLogger::new(0);
+    let mut manager = make_manager(false);
+    let mut loggers: Vec<Box<SendProcessLogger<TestConf>>> =
+        vec![Box::new(recorder), Box::new(ControlOnNode(1, LoggerControl::Stop))];
+    let report = drive_loggers_threaded(&mut manager, &mut loggers, &ThreadedDriverOptions::new(1, ThreadedBackPressure::Block));
+    assert!(matches!(report.termination,
+        TerminationReason::StoppedByLogger { logger_index: 1, control: LoggerControl::Stop, .. }));
+    let kinds = kinds.lock().unwrap();
+    assert_eq!(kinds.last().map(String::as_str), Some("terminate"));
+    assert!(kinds.len() < 12, "the exploration was cut short");
+}
+
+/// Asks to stop after the current node when notified that the given leaf has no children,
+/// once the gate is opened.
+struct GatedStopAfterLeaf(u32, mpsc::Receiver<()>);
+
+impl AbstractProcessLogger<TestConf> for GatedStopAfterLeaf {
+    fn log_notify_node_without_children(&mut self, _ctx: &(), _meta: &EventMeta<TestConf>, node_id: u32) -> LoggerResult {
+        if node_id != self.0 {
+            return Ok(LoggerControl::Continue);
+        }
+        let _ = self.1.recv();
+        Ok(LoggerControl::StopAfterCurrentNode)
+    }
+}
+
+#[test]
+fn threaded_driver_stops_after_the_leaf_the_stop_was_requested_from() {
+    // leaf 3 asks to stop once node 2 is being expanded, i.e. after AllChildrenProcessed 1 has been sent:
+    // with room for a single event in its channel, the exploration thread cannot go further before noticing it
+    let (open, gate) = mpsc::channel();
+    let (recorder, kinds) = EventKindsLogger::new(0);
+    let mut manager = make_manager_with_step_filter(Box::new(OpenGateOnExpansion(2, open)));
+    let mut loggers: Vec<Box<SendProcessLogger<TestConf>>> =
+        vec![Box::new(recorder), Box::new(GatedStopAfterLeaf(3, gate))];
+    let report = drive_loggers_threaded(&mut manager, &mut loggers, &ThreadedDriverOptions::new(1, ThreadedBackPressure::Block));
+    assert!(matches!(report.termination,
+        TerminationReason::StoppedByLogger { logger_index: 1, control: LoggerControl::StopAfterCurrentNode, .. }));
+
+    // the leaf was already complete, rather than waiting for AllChildrenProcessed 2
+    let kinds = kinds.lock().unwrap();
+    assert!(kinds.contains(&"done 1".to_string()));
+    assert!(!kinds.contains(&"leaf 4".to_string()) && !kinds.contains(&"done 2".to_string()));
+    assert_eq!(kinds.last().map(String::as_str), Some("terminate"));
+}
+
 // === Tests: nodesprint logger =================================================
 
 struct PlainTextNodePrinter;
 
 impl NodesPrintProcessPrinter<TestConf> for PlainTextNodePrinter {
     fn should_print_node(&self, _ctx: &(), _node: &Node) -> bool { true }
-    fn print_node(&self, _ctx: &(), node: &Node, path: &Path) {
-        std::fs::write(path, node.0.to_string()).unwrap();
+    fn print_node(&self, _ctx: &(), node: &Node, path: &Path) -> Result<(), LoggerError> {
+        std::fs::write(path, node.0.to_string()).map_err(|e| LoggerError::io("printing a node", e))
     }
 }
 
@@ -375,11 +986,37 @@ fn nodesprint_creates_one_file_per_discovered_node() {
             out.to_str().unwrap().to_string(),
         )),
     ];
-    drive_loggers(&mut manager, &mut loggers);
+    assert!(drive_loggers(&mut manager, &mut loggers).is_success());
     // BFS no-memo: 4 nodes discovered → 4 files
     assert_eq!(std::fs::read_dir(&out).unwrap().count(), 4);
 }
 
+struct FailingNodePrinter;
+
+impl NodesPrintProcessPrinter<TestConf> for FailingNodePrinter {
+    fn should_print_node(&self, _ctx: &(), _node: &Node) -> bool { true }
+    fn print_node(&self, _ctx: &(), _node: &Node, _path: &Path) -> Result<(), LoggerError> {
+        Err(LoggerError::Custom("printer failure".to_string()))
+    }
+}
+
+#[test]
+fn nodesprint_reports_the_errors_of_the_printer() {
+    let out = std::env::temp_dir().join("gpm_test_nodesprint_failing");
+    let mut manager = make_manager(false);
+    let mut loggers: Vec<Box<dyn AbstractProcessLogger<TestConf>>> = vec![
+        Box::new(GenericNodesPrintLogger::new(
+            Box::new(FailingNodePrinter),
+            "n".to_string(), "txt".to_string(),
+            out.to_str().unwrap().to_string(),
+        )),
+    ];
+    let report = drive_loggers(&mut manager, &mut loggers);
+    assert_eq!(report.failures.len(), 1);
+    assert_eq!(report.failures[0].event, LoggerEventKind::NewNode { node_id: 1 });
+    assert!(matches!(&report.failures[0].error, LoggerError::Custom(msg) if msg == "printer failure"));
+}
+
 #[test]
 fn nodesprint_with_memo_creates_fewer_files() {
     let out = std::env::temp_dir().join("gpm_test_nodesprint_memo");
@@ -392,7 +1029,7 @@ fn nodesprint_with_memo_creates_fewer_files() {
             out.to_str().unwrap().to_string(),
         )),
     ];
-    drive_loggers(&mut manager, &mut loggers);
+    assert!(drive_loggers(&mut manager, &mut loggers).is_success());
     // With memo: 3 unique nodes → 3 files
     assert_eq!(std::fs::read_dir(&out).unwrap().count(), 3);
 }
@@ -415,9 +1052,9 @@ impl StepsTraceProcessPrinter<TestConf, Trace> for TracePrinter {
         Trace(v)
     }
     fn should_print_on_node_reached(&self, _ctx: &(), _node: &Node) -> bool { true }
-    fn print_object(&self, _ctx: &(), trace: &Trace, path: &Path) {
+    fn print_object(&self, _ctx: &(), trace: &Trace, path: &Path) -> Result<(), LoggerError> {
         let s: Vec<String> = trace.0.iter().map(|v| v.to_string()).collect();
-        std::fs::write(path, s.join(",")).unwrap();
+        std::fs::write(path, s.join(",")).map_err(|e| LoggerError::io("printing a trace", e))
     }
 }
 
@@ -433,7 +1070,7 @@ fn stepstrace_creates_one_file_per_edge() {
             out.to_str().unwrap().to_string(),
         )),
     ];
-    drive_loggers(&mut manager, &mut loggers);
+    assert!(drive_loggers(&mut manager, &mut loggers).is_success());
     // BFS no-memo: 3 edges (0→2, 0→1, 2→1) → 3 trace files
     assert_eq!(std::fs::read_dir(&out).unwrap().count(), 3);
 }
@@ -450,7 +1087,7 @@ fn stepstrace_file_contents_are_correct_paths() {
             out.to_str().unwrap().to_string(),
         )),
     ];
-    drive_loggers(&mut manager, &mut loggers);
+    assert!(drive_loggers(&mut manager, &mut loggers).is_success());
 
     let mut contents: Vec<String> = std::fs::read_dir(&out).unwrap()
         .map(|e| std::fs::read_to_string(e.unwrap().path()).unwrap())
@@ -475,8 +1112,1041 @@ fn stepstrace_dedup_prevents_duplicate_traces() {
             out.to_str().unwrap().to_string(),
         )),
     ];
-    drive_loggers(&mut manager, &mut loggers);
+    assert!(drive_loggers(&mut manager, &mut loggers).is_success());
 
     // "1", "2", "2,1" are all distinct → still 3 files even with dedup
     assert_eq!(std::fs::read_dir(&out).unwrap().count(), 3);
 }
+
+// === Tests: statistics =======================================================
+
+fn run_statistics(memoized: bool, name: &str) -> (std::path::PathBuf, ExplorationStatistics) {
+    let out = std::env::temp_dir().join("gpm_test_statistics");
+    let mut manager = make_manager(memoized);
+    let mut loggers = LoggerRegistry::new();
+    let statistics = loggers.add(GenericStatisticsLogger::new(out.to_str().unwrap().to_string(), name.to_string()));
+    assert!(loggers.drive(&mut manager).is_success());
+    (out, loggers.take_product(statistics).unwrap())
+}
+
+#[test]
+fn statistics_count_nodes_steps_leaves_and_histograms_no_memo() {
+    let (_, stats) = run_statistics(false, "no_memo");
+    assert_eq!((stats.nodes, stats.steps, stats.filtrations, stats.leaves, stats.back_edges), (4, 3, 0, 2, 0));
+    assert_eq!(stats.depth_histogram.into_iter().collect::<Vec<_>>(), vec![(0, 1), (1, 2), (2, 1)]);
+    // node 0 has 2 children, node 2 has 1, both occurrences of node 1 have none
+    assert_eq!(stats.out_degree_histogram.into_iter().collect::<Vec<_>>(), vec![(0, 2), (1, 1), (2, 1)]);
+    assert_eq!(stats.termination.as_deref(), Some("exploration completed"));
+}
+
+#[test]
+fn statistics_count_memoized_back_edge() {
+    let (out, stats) = run_statistics(true, "memo");
+    assert_eq!((stats.nodes, stats.steps, stats.leaves, stats.back_edges), (3, 3, 1, 1));
+    assert!(stats.max_frontier_size >= 1);
+
+    let json = std::fs::read_to_string(out.join("memo.json")).unwrap();
+    assert_eq!(json, stats.to_json());
+    assert!(json.contains("\"back_edges\": 1"));
+    let depths = std::fs::read_to_string(out.join("memo_depth.csv")).unwrap();
+    assert_eq!(depths, "depth,nodes\n0,1\n1,2\n");
+    let out_degrees = std::fs::read_to_string(out.join("memo_out_degree.csv")).unwrap();
+    assert_eq!(out_degrees, "out_degree,nodes\n0,1\n1,1\n2,1\n");
+}
+
+// === Tests: GraphML ==========================================================
+
+#[cfg(feature = "graphml")]
+mod graphml {
+    use super::*;
+    use graph_process_manager_loggers::graphml::attribute::GraphMLAttributeValue;
+    use graph_process_manager_loggers::graphml::describer::GraphMLProcessDescriber;
+    use graph_process_manager_loggers::graphml::logger::GenericGraphMLLogger;
+
+    struct ValueDescriber;
+
+    impl GraphMLProcessDescriber<TestConf> for ValueDescriber {
+        fn describe_node(&self, _ctx: &(), node: &Node) -> Vec<(String, GraphMLAttributeValue)> {
+            vec![("value".to_string(), (node.0 as i64).into()), ("terminal".to_string(), (node.0 == 1).into())]
+        }
+        fn describe_step(&self, _ctx: &(), step: &Step) -> Vec<(String, GraphMLAttributeValue)> {
+            vec![("value".to_string(), format!("to {}", step.0).into())]
+        }
+        fn describe_filtration_result(&self, _ctx: &(), _result: &()) -> Vec<(String, GraphMLAttributeValue)> {
+            vec![("value".to_string(), "<blocked & dropped>".into())]
+        }
+    }
+
+    #[test]
+    fn graphml_logger_writes_nodes_steps_and_typed_keys() {
+        let out = std::env::temp_dir().join("gpm_test_graphml");
+        let mut manager = make_manager(true);
+        let mut loggers = LoggerRegistry::new();
+        let graphml = loggers.add(GenericGraphMLLogger::new(
+            Box::new(ValueDescriber), out.to_str().unwrap().to_string(), "memo".to_string()));
+        assert!(loggers.drive(&mut manager).is_success());
+        let document = loggers.take_product(graphml).unwrap();
+
+        assert_eq!(std::fs::read_to_string(out.join("memo.graphml")).unwrap(), document);
+        assert_eq!(document.matches("<node id=").count(), 3);
+        // the memoized back-edge 2→1 is an edge towards the already written node 1
+        assert_eq!(document.matches("<edge id=").count(), 3);
+        assert!(document.contains("<key id=\"d1\" for=\"node\" attr.name=\"value\" attr.type=\"long\"/>"));
+        assert!(document.contains("<key id=\"d2\" for=\"node\" attr.name=\"terminal\" attr.type=\"boolean\"/>"));
+        assert!(document.contains("<key id=\"d4\" for=\"edge\" attr.name=\"value\" attr.type=\"string\"/>"));
+    }
+
+    #[test]
+    fn graphml_filtration_results_are_nodes_with_escaped_data() {
+        let out = std::env::temp_dir().join("gpm_test_graphml");
+        let mut manager = make_manager_with_step_filter(Box::new(BlockStepTo(2)));
+        let mut loggers = LoggerRegistry::new();
+        let graphml = loggers.add(GenericGraphMLLogger::new(
+            Box::new(ValueDescriber), out.to_str().unwrap().to_string(), "filtered".to_string()));
+        assert!(loggers.drive(&mut manager).is_success());
+        let document = loggers.take_product(graphml).unwrap();
+
+        assert!(document.contains("<node id=\"f1\">"));
+        assert!(document.contains("&lt;blocked &amp; dropped&gt;"));
+        // "value" is a long for process nodes and a string for the filtration result
+        assert!(document.contains("attr.name=\"value\" attr.type=\"string\"/>"));
+        assert!(!document.contains("attr.type=\"long\""));
+    }
+
+    // describes the steps with the key reserved by the logger
+    struct KindDescriber;
+
+    impl GraphMLProcessDescriber<TestConf> for KindDescriber {
+        fn describe_node(&self, ctx: &(), node: &Node) -> Vec<(String, GraphMLAttributeValue)> {
+            ValueDescriber.describe_node(ctx, node)
+        }
+        fn describe_step(&self, _ctx: &(), step: &Step) -> Vec<(String, GraphMLAttributeValue)> {
+            vec![("kind".to_string(), format!("to {}", step.0).into())]
+        }
+        fn describe_filtration_result(&self, ctx: &(), result: &()) -> Vec<(String, GraphMLAttributeValue)> {
+            ValueDescriber.describe_filtration_result(ctx, result)
+        }
+    }
+
+    #[test]
+    fn graphml_rejects_attributes_with_the_reserved_kind_key() {
+        let out = std::env::temp_dir().join("gpm_test_graphml");
+        let mut manager = make_manager(true);
+        let mut loggers = LoggerRegistry::new();
+        loggers.add(GenericGraphMLLogger::new(
+            Box::new(KindDescriber), out.to_str().unwrap().to_string(), "kind".to_string()));
+        let report = loggers.drive(&mut manager);
+
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].event, LoggerEventKind::NewStep { origin_node_id: 1, target_node_id: 2 });
+        assert!(matches!(&report.failures[0].error,
+            LoggerError::Custom(message) if message == "describing a step : the \"kind\" key is reserved by the logger"));
+    }
+}
+
+// === Tests: GEXF =============================================================
+
+#[cfg(feature = "gexf")]
+mod gexf {
+    use super::*;
+    use graph_process_manager_loggers::gexf::logger::GenericGexfLogger;
+    use graph_process_manager_loggers::graphml::attribute::GraphMLAttributeValue;
+    use graph_process_manager_loggers::graphml::describer::GraphMLProcessDescriber;
+
+    struct ValueDescriber;
+
+    impl GraphMLProcessDescriber<TestConf> for ValueDescriber {
+        fn describe_node(&self, _ctx: &(), node: &Node) -> Vec<(String, GraphMLAttributeValue)> {
+            vec![("value".to_string(), (node.0 as i64).into())]
+        }
+        fn describe_step(&self, _ctx: &(), _step: &Step) -> Vec<(String, GraphMLAttributeValue)> {
+            vec![]
+        }
+        fn describe_filtration_result(&self, _ctx: &(), _result: &()) -> Vec<(String, GraphMLAttributeValue)> {
+            vec![]
+        }
+    }
+
+    #[test]
+    fn gexf_elements_start_at_the_discovery_index() {
+        let out = std::env::temp_dir().join("gpm_test_gexf");
+        let mut manager = make_manager(true);
+        let mut loggers = LoggerRegistry::new();
+        let gexf = loggers.add(GenericGexfLogger::new(
+            Box::new(ValueDescriber), out.to_str().unwrap().to_string(), "memo".to_string()));
+        assert!(loggers.drive(&mut manager).is_success());
+        let document = loggers.take_product(gexf).unwrap();
+
+        assert_eq!(std::fs::read_to_string(out.join("memo.gexf")).unwrap(), document);
+        assert!(document.contains("<graph mode=\"dynamic\" defaultedgetype=\"directed\" timeformat=\"integer\">"));
+        assert!(document.contains("<attribute id=\"1\" title=\"value\" type=\"long\"/>"));
+        // BFS with memo : node 0, then 2, then 1
+        assert!(document.contains("<node id=\"n1\" label=\"n1\" start=\"0\">"));
+        assert!(document.contains("<attvalue for=\"1\" value=\"2\"/>"));
+        let starts: Vec<&str> = document
+            .lines()
+            .filter(|line| line.trim_start().starts_with("<edge "))
+            .map(|line| line.split("start=\"").nth(1).unwrap().split('"').next().unwrap())
+            .collect();
+        // the back-edge 2→1 is logged after node 1 was discovered
+        assert_eq!(starts, vec!["1", "2", "2"]);
+    }
+}
+
+// === Tests: Mermaid ==========================================================
+
+#[cfg(feature = "mermaid")]
+mod mermaid {
+    use super::*;
+    use graph_process_manager_loggers::mermaid::format::MermaidProcessLoggerLayout;
+    use graph_process_manager_loggers::mermaid::labeller::MermaidProcessLabeller;
+    use graph_process_manager_loggers::mermaid::logger::GenericMermaidLogger;
+
+    struct ValueLabeller;
+
+    impl MermaidProcessLabeller<TestConf> for ValueLabeller {
+        fn node_label(&self, _ctx: &(), node: &Node) -> String { format!("node \"{}\"", node.0) }
+        fn step_label(&self, _ctx: &(), step: &Step) -> String { format!("to #{}", step.0) }
+        fn filtration_label(&self, _ctx: &(), _result: &()) -> String { "blocked".to_string() }
+        fn node_phase(&self, _ctx: &(), node: &Node) -> Option<usize> { (node.0 == 2).then_some(7) }
+    }
+
+    fn run_mermaid(manager: &mut GenericProcessManager<TestConf>, node_limit: Option<usize>) -> String {
+        let out = std::env::temp_dir().join("gpm_test_mermaid");
+        let mut loggers = LoggerRegistry::new();
+        let mermaid = loggers.add(GenericMermaidLogger::new(
+            Box::new(ValueLabeller), MermaidProcessLoggerLayout::Vertical, node_limit,
+            out.to_str().unwrap().to_string(), "flow".to_string()));
+        assert!(loggers.drive(manager).is_success());
+        let document = loggers.take_product(mermaid).unwrap();
+        assert_eq!(std::fs::read_to_string(out.join("flow.mmd")).unwrap(), document);
+        document
+    }
+
+    #[test]
+    fn mermaid_flowchart_has_labels_phases_and_filtration_class() {
+        let document = run_mermaid(&mut make_manager_with_step_filter(Box::new(BlockStepTo(1))), None);
+        assert!(document.starts_with("flowchart TD\n"));
+        assert!(document.contains("    n1[\"node #quot;0#quot;\"]\n"));
+        assert!(document.contains("    subgraph phase7 [\"phase 7\"]\n        n2[\"node #quot;2#quot;\"]\n    end\n"));
+        // the # of the label is escaped before the entity codes are added
+        assert!(document.contains("    n1 -->|\"to #35;2\"| n2\n"));
+        // the steps towards 1 from 0 and from 2 are filtered
+        assert!(document.contains("    f2[\"blocked\"]:::filtration\n"));
+        assert!(document.contains("    n2 -.-> f2\n"));
+        assert!(!document.contains(":::truncated"));
+    }
+
+    #[test]
+    fn mermaid_node_limit_truncates_the_flowchart() {
+        let document = run_mermaid(&mut make_manager(false), Some(2));
+        assert_eq!(document.matches("-->").count(), 1);
+        assert!(document.contains(
+            "truncated[\"truncated after 2 nodes : 2 more nodes, 2 more steps and 0 more filtrations\"]:::truncated"));
+    }
+}
+
+// === Tests: Aldebaran ========================================================
+
+#[cfg(feature = "aut")]
+mod aut {
+    use super::*;
+    use graph_process_manager_loggers::aut::labeller::AutProcessLabeller;
+    use graph_process_manager_loggers::aut::logger::GenericAutLogger;
+
+    struct StepLabeller;
+
+    impl AutProcessLabeller<TestConf> for StepLabeller {
+        fn step_label(&self, _ctx: &(), step: &Step) -> String { format!("go\"{}\"", step.0) }
+    }
+
+    fn run_aut(mut manager: GenericProcessManager<TestConf>, filtrations_to_sink: bool, name: &str) -> String {
+        let out = std::env::temp_dir().join("gpm_test_aut");
+        let mut loggers = LoggerRegistry::new();
+        let aut = loggers.add(GenericAutLogger::new(
+            Box::new(StepLabeller), filtrations_to_sink, out.to_str().unwrap().to_string(), name.to_string()));
+        assert!(loggers.drive(&mut manager).is_success());
+        let document = loggers.take_product(aut).unwrap();
+        assert_eq!(std::fs::read_to_string(out.join(format!("{}.aut", name))).unwrap(), document);
+        document
+    }
+
+    #[test]
+    fn aut_lts_of_memoized_exploration_has_dense_states() {
+        // BFS with memo : states 0 (node 0), 1 (node 2), 2 (node 1)
+        assert_eq!(
+            run_aut(make_manager(true), false, "memo"),
+            "des (0, 3, 3)\n(0, \"go'2'\", 1)\n(0, \"go'1'\", 2)\n(1, \"go'1'\", 2)\n"
+        );
+    }
+
+    #[test]
+    fn aut_filtered_branches_lead_to_a_sink_state() {
+        let manager = make_manager_with_step_filter(Box::new(BlockStepTo(1)));
+        assert_eq!(run_aut(manager, false, "no_sink"), "des (0, 1, 2)\n(0, \"go'2'\", 1)\n");
+        let manager = make_manager_with_step_filter(Box::new(BlockStepTo(1)));
+        let document = run_aut(manager, true, "sink");
+        assert!(document.starts_with("des (0, 3, 3)\n"));
+        assert!(document.contains("(0, \"filtered\", 2)\n"));
+        assert!(document.contains("(1, \"filtered\", 2)\n"));
+    }
+
+    struct MultilineLabeller;
+
+    impl AutProcessLabeller<TestConf> for MultilineLabeller {
+        fn step_label(&self, _ctx: &(), step: &Step) -> String { format!("go\r\nto\n\"{}\"\r", step.0) }
+    }
+
+    #[test]
+    fn aut_labels_are_written_on_a_single_line() {
+        let mut manager = make_manager(true);
+        let mut loggers = LoggerRegistry::new();
+        let out = std::env::temp_dir().join("gpm_test_aut");
+        let aut = loggers.add(GenericAutLogger::new(
+            Box::new(MultilineLabeller), false, out.to_str().unwrap().to_string(), "multiline".to_string()));
+        assert!(loggers.drive(&mut manager).is_success());
+        assert_eq!(
+            loggers.take_product(aut).unwrap(),
+            "des (0, 3, 3)\n(0, \"go to '2' \", 1)\n(0, \"go to '1' \", 2)\n(1, \"go to '1' \", 2)\n"
+        );
+    }
+}
+
+// === Tests: PRISM ============================================================
+
+#[cfg(feature = "prism")]
+mod prism {
+    use super::*;
+    use graph_process_manager_loggers::prism::describer::PrismProcessDescriber;
+    use graph_process_manager_loggers::prism::logger::GenericPrismLogger;
+    use graph_process_manager_loggers::prism::model::PrismExplicitModel;
+    use graph_process_manager_loggers::report::LoggerFailure;
+
+    struct UniformDescriber;
+
+    impl PrismProcessDescriber<TestConf> for UniformDescriber {}
+
+    struct WeightedDescriber;
+
+    impl PrismProcessDescriber<TestConf> for WeightedDescriber {
+        fn state_variables(&self) -> Vec<String> { vec!["value".to_string()] }
+        fn state_valuation(&self, _ctx: &(), node: &Node) -> Option<Vec<i64>> { Some(vec![node.0 as i64]) }
+        fn step_weight(&self, _ctx: &(), step: &Step) -> f64 { if step.0 == 2 { 3.0 } else { 1.0 } }
+        fn labels(&self) -> Vec<String> { vec!["terminal".to_string()] }
+        fn has_label(&self, _ctx: &(), node: &Node, label: &str) -> bool { label == "terminal" && node.0 == 1 }
+    }
+
+    fn run_prism(describer: Box<dyn PrismProcessDescriber<TestConf>>, name: &str) -> PrismExplicitModel {
+        let out = std::env::temp_dir().join("gpm_test_prism");
+        let mut manager = make_manager(true);
+        let mut loggers = LoggerRegistry::new();
+        let prism = loggers.add(GenericPrismLogger::new(describer, out.to_str().unwrap().to_string(), name.to_string()));
+        assert!(loggers.drive(&mut manager).is_success());
+        let model = loggers.take_product(prism).unwrap();
+        assert_eq!(std::fs::read_to_string(out.join(format!("{}.tra", name))).unwrap(), model.transitions);
+        model
+    }
+
+    #[test]
+    fn prism_default_probabilities_are_uniform() {
+        // BFS with memo : states 0 (node 0), 1 (node 2), 2 (node 1)
+        let model = run_prism(Box::new(UniformDescriber), "uniform");
+        assert_eq!(model.states, "(node)\n0:(0)\n1:(1)\n2:(2)\n");
+        assert_eq!(model.transitions, "3 3\n0 1 0.5\n0 2 0.5\n1 2 1\n");
+        assert_eq!(model.labels, "0=\"init\" 1=\"deadlock\"\n0: 0\n2: 1\n");
+    }
+
+    #[test]
+    fn prism_weights_valuations_and_labels_come_from_the_describer() {
+        let model = run_prism(Box::new(WeightedDescriber), "weighted");
+        assert_eq!(model.states, "(value)\n0:(0)\n1:(2)\n2:(1)\n");
+        assert_eq!(model.transitions, "3 3\n0 1 0.75\n0 2 0.25\n1 2 1\n");
+        assert_eq!(model.labels, "0=\"init\" 1=\"deadlock\" 2=\"terminal\"\n0: 0\n2: 1 2\n");
+    }
+
+    struct InvalidLabelDescriber;
+
+    impl PrismProcessDescriber<TestConf> for InvalidLabelDescriber {
+        fn labels(&self) -> Vec<String> { vec!["terminal".to_string(), "2 steps".to_string()] }
+    }
+
+    // declares two state variables but gives a single value
+    struct ShortValuationDescriber;
+
+    impl PrismProcessDescriber<TestConf> for ShortValuationDescriber {
+        fn state_variables(&self) -> Vec<String> { vec!["value".to_string(), "depth".to_string()] }
+        fn state_valuation(&self, _ctx: &(), node: &Node) -> Option<Vec<i64>> { Some(vec![node.0 as i64]) }
+    }
+
+    fn prism_failure(describer: Box<dyn PrismProcessDescriber<TestConf>>) -> LoggerFailure {
+        let out = std::env::temp_dir().join("gpm_test_prism");
+        let mut manager = make_manager(true);
+        let mut loggers = LoggerRegistry::new();
+        loggers.add(GenericPrismLogger::new(describer, out.to_str().unwrap().to_string(), "invalid".to_string()));
+        let mut report = loggers.drive(&mut manager);
+        assert_eq!(report.failures.len(), 1);
+        report.failures.remove(0)
+    }
+
+    #[test]
+    fn prism_rejects_labels_that_are_not_identifiers() {
+        let failure = prism_failure(Box::new(InvalidLabelDescriber));
+        assert_eq!(failure.event, LoggerEventKind::InitializeProcess);
+        assert!(matches!(failure.error,
+            LoggerError::Custom(message) if message == "label \"2 steps\" is not a PRISM identifier"));
+    }
+
+    #[test]
+    fn prism_rejects_valuations_without_a_value_per_state_variable() {
+        let failure = prism_failure(Box::new(ShortValuationDescriber));
+        assert_eq!(failure.event, LoggerEventKind::NewNode { node_id: 1 });
+        assert!(matches!(failure.error,
+            LoggerError::Custom(message) if message == "node 1 has 1 values for the 2 state variables"));
+    }
+}
+
+// === Tests: GraphViz =========================================================
+
+#[cfg(feature = "graphviz")]
+mod graphviz {
+    use super::*;
+    use graphviz_dot_builder::colors::GraphvizColor;
+    use graphviz_dot_builder::item::node::style::{GraphvizNodeStyleItem, GvNodeShape, GvNodeStyleKind};
+    use graphviz_dot_builder::traits::{DotTranslatable, GraphVizOutputFormat};
+    use graph_process_manager_loggers::graphviz::drawers::drawer::GraphVizProcessDrawer;
+    use graph_process_manager_loggers::graphviz::format::{GraphVizLoggerStepFormat, GraphVizNodeExplorationStatus, GraphVizProcessLoggerLayout};
+    use graph_process_manager_loggers::graphviz::item::{BuiltinGraphvizLoggerDefaultGvItemStyle, BuiltinGraphvizLoggerItemStyle};
+    use graph_process_manager_loggers::graphviz::heatmap::{GraphVizHeatmapOptions, GraphVizNodeMetric};
+    use graph_process_manager_loggers::graphviz::limits::GraphVizOutputLimits;
+    use graph_process_manager_loggers::graphviz::logger::{GenericGraphVizLogger, GenericGraphVizLoggerConfiguration};
+    use graph_process_manager_loggers::graphviz::snapshot::GraphVizSnapshotOptions;
+
+    pub(super) struct LabelDrawer;
+
+    fn label_style(label: String) -> BuiltinGraphvizLoggerItemStyle {
+        BuiltinGraphvizLoggerItemStyle::Default(BuiltinGraphvizLoggerDefaultGvItemStyle::new(
+            GvNodeShape::Rectangle, label, 12, None,
+            GraphvizColor::black, GraphvizColor::black, GraphvizColor::white,
+        ))
+    }
+
+    impl GraphVizProcessDrawer<TestConf> for LabelDrawer {
+        fn draw_node_view(&self, _ctx: &(), node: &Node, _view_index: usize, _path: &Path) -> BuiltinGraphvizLoggerItemStyle {
+            label_style(format!("node {}", node.0))
+        }
+        fn draw_step(&self, _ctx: &(), step: &Step, _path: &Path) -> BuiltinGraphvizLoggerItemStyle {
+            label_style(format!("to {}", step.0))
+        }
+        fn step_edge_color(&self, _ctx: &(), _step: &Step) -> GraphvizColor { GraphvizColor::black }
+        fn draw_filter(&self, _ctx: &(), _result: &(), _path: &Path) -> BuiltinGraphvizLoggerItemStyle {
+            label_style("filtered".to_string())
+        }
+        fn filter_edge_color(&self, _ctx: &(), _result: &()) -> GraphvizColor { GraphvizColor::red }
+    }
+
+    pub(super) fn make_graphviz_logger(name: &str) -> GenericGraphVizLogger<TestConf> {
+        make_graphviz_logger_drawing_with(name, Box::new(LabelDrawer))
+    }
+
+    fn make_graphviz_logger_drawing_with<Conf: AbstractProcessConfiguration + 'static>(
+        name: &str,
+        drawer: Box<dyn GraphVizProcessDrawer<Conf>>,
+    ) -> GenericGraphVizLogger<Conf> {
+        let out = std::env::temp_dir().join("gpm_test_graphviz").join(name);
+        let configuration = GenericGraphVizLoggerConfiguration::new(
+            GraphVizOutputFormat::svg,
+            out.join("temp").to_str().unwrap().to_string(),
+            out.to_str().unwrap().to_string(),
+            "graph".to_string(),
+        );
+        GenericGraphVizLogger::new(configuration, drawer, None, GraphVizProcessLoggerLayout::Vertical)
+    }
+
+    #[test]
+    fn graphviz_streaming_writes_the_dot_file_as_events_arrive() {
+        let mut logger = make_graphviz_logger("streaming");
+        logger.configuration.streaming = true;
+        let dot_path = logger.get_dot_file_path();
+        let mut manager = make_manager(true);
+        let mut loggers = LoggerRegistry::new();
+        let graphviz = loggers.add(logger);
+
+        let mut driver = loggers.driver(&mut manager, &DriveLoggersOptions::default());
+        driver.run_until(|event| matches!(event, ExplorationEvent::NewStep { .. }));
+        let partial = std::fs::read_to_string(&dot_path).unwrap();
+        assert!(partial.starts_with("strict digraph G {\ncompound=true;\nrankdir=tb;"));
+        assert!(partial.contains("\n\t_n1 [") && partial.contains("\n\t_n2 ["));
+        // the edges of the step are written along with it
+        assert!(partial.contains("\n\t_n1->_s_1_2 [") && partial.contains("\n\t_s_1_2->_n2 ["));
+        assert!(!partial.contains("_n3") && !partial.ends_with('}'));
+        // rendering fails where the dot binary is not installed, the .dot file is complete anyway
+        let _ = driver.finish();
+
+        let complete = std::fs::read_to_string(&dot_path).unwrap();
+        assert!(complete.starts_with(&partial) && complete.ends_with("\n}"));
+        assert_eq!(complete.matches("->").count(), 6);
+        // nothing but the style is kept in memory
+        let graph = loggers.take_product(graphviz).unwrap();
+        assert!(graph.items.is_empty() && graph.edges.is_empty());
+    }
+
+    #[test]
+    fn graphviz_snapshots_are_numbered_frames_highlighting_the_last_node() {
+        let mut logger = make_graphviz_logger("snapshots");
+        let mut options = GraphVizSnapshotOptions::every_events(3);
+        options.render = false;
+        options.html_player = true;
+        logger.configuration.snapshots = Some(options);
+        let frames_folder = logger.get_frames_folder();
+        let mut manager = make_manager(true);
+        let mut loggers = LoggerRegistry::new();
+        loggers.add(logger);
+
+        let mut driver = loggers.driver(&mut manager, &DriveLoggersOptions::default());
+        let events = driver.run_until(|_| false);
+        let _ = driver.finish();
+
+        let frames = events.div_ceil(3) as usize;
+        let mut files: Vec<String> = std::fs::read_dir(&frames_folder).unwrap()
+            .map(|entry| entry.unwrap().file_name().to_str().unwrap().to_string())
+            .collect();
+        files.sort();
+        assert_eq!(files.len(), frames + 1);
+        assert_eq!(files[0], "frame_00000.dot");
+        assert_eq!(files[frames], "player.html");
+
+        // the first frame is taken after NewNode 1, NewNode 2 and NewStep 1→2
+        let first = std::fs::read_to_string(frames_folder.join("frame_00000.dot")).unwrap();
+        // the node is restyled in the copy of the graph the frame is drawn from
+        assert!(first.contains("\n\t_n2 [shape=rectangle,label=\"node 2\""));
+        assert!(first.contains(",color=red,penwidth=3];\n\t_s_1_2 ["));
+        assert_eq!(first.matches("\t_n2 [").count(), 1);
+        assert!(!first.contains("_n3"));
+        let player = std::fs::read_to_string(frames_folder.join("player.html")).unwrap();
+        assert!(player.contains("const frames = [\"frame_00000.svg\", \"frame_00001.svg\""));
+    }
+
+    #[test]
+    fn graphviz_edge_label_format_draws_each_step_as_a_single_labelled_edge() {
+        let mut logger = make_graphviz_logger("edge_labels");
+        logger.configuration.step_format = GraphVizLoggerStepFormat::EdgeLabel;
+        let mut manager = make_manager(true);
+        let mut loggers = LoggerRegistry::new();
+        let graphviz = loggers.add(logger);
+        let _ = loggers.drive(&mut manager);
+
+        let graph = loggers.take_product(graphviz).unwrap();
+        // the 3 process nodes, and no step node
+        assert_eq!(graph.items.len(), 3);
+        let edges: Vec<String> = graph.edges.iter().map(|edge| edge.to_dot_string()).collect();
+        assert_eq!(edges.len(), 3);
+        assert_eq!(edges[0], "_n1->_n2 [arrowhead=vee,color=black,label=\"to 2\",fontcolor=black,fontsize=12];");
+    }
+
+    // draws the steps as images, which cannot be drawn on edges
+    struct ImageStepDrawer;
+
+    impl GraphVizProcessDrawer<TestConf> for ImageStepDrawer {
+        fn draw_node_view(&self, ctx: &(), node: &Node, view_index: usize, path: &Path) -> BuiltinGraphvizLoggerItemStyle {
+            LabelDrawer.draw_node_view(ctx, node, view_index, path)
+        }
+        fn draw_step(&self, _ctx: &(), _step: &Step, _path: &Path) -> BuiltinGraphvizLoggerItemStyle {
+            BuiltinGraphvizLoggerItemStyle::CustomImage
+        }
+        fn step_label_text(&self, _ctx: &(), step: &Step) -> Option<String> {
+            Some(format!("step to {}", step.0))
+        }
+        fn step_edge_color(&self, _ctx: &(), _step: &Step) -> GraphvizColor { GraphvizColor::black }
+        fn draw_filter(&self, ctx: &(), result: &(), path: &Path) -> BuiltinGraphvizLoggerItemStyle {
+            LabelDrawer.draw_filter(ctx, result, path)
+        }
+        fn filter_edge_color(&self, _ctx: &(), _result: &()) -> GraphvizColor { GraphvizColor::red }
+    }
+
+    #[test]
+    fn graphviz_edge_label_format_labels_the_steps_of_image_drawers() {
+        let mut logger = make_graphviz_logger("edge_labels_images");
+        logger.drawer = Box::new(ImageStepDrawer);
+        logger.configuration.step_format = GraphVizLoggerStepFormat::EdgeLabel;
+        let mut manager = make_manager(true);
+        let mut loggers = LoggerRegistry::new();
+        let graphviz = loggers.add(logger);
+        let _ = loggers.drive(&mut manager);
+
+        let edges: Vec<String> = loggers.take_product(graphviz).unwrap()
+            .edges.iter().map(|edge| edge.to_dot_string()).collect();
+        assert_eq!(edges[0], "_n1->_n2 [arrowhead=vee,color=black,label=\"step to 2\"];");
+    }
+
+    fn run_graphviz_collapsing_chains(manager: &mut GenericProcessManager<TestConf>, name: &str) -> Vec<String> {
+        let mut logger = make_graphviz_logger(name);
+        logger.configuration.collapse_linear_chains = true;
+        let mut loggers = LoggerRegistry::new();
+        let graphviz = loggers.add(logger);
+        let _ = loggers.drive(manager);
+        let graph = loggers.take_product(graphviz).unwrap();
+        graph.items.iter().map(|item| item.to_dot_string()).chain(graph.edges.iter().map(|edge| edge.to_dot_string())).collect()
+    }
+
+    #[test]
+    fn graphviz_collapses_linear_chains_at_termination() {
+        // without memo : node 2 (id 2) is the only child of node 0 (id 1) and has a single child (id 4)
+        let statements = run_graphviz_collapsing_chains(&mut make_manager(false), "chains");
+        assert!(!statements.iter().any(|statement| statement.contains("_n2") || statement.contains("_s_2_4")));
+        // the step from node 0 to node 1 (id 3) is kept
+        assert_eq!(statements.iter().filter(|statement| statement.starts_with("_s_1_3")).count(), 2);
+        assert!(statements.contains(&"_n1->_n4 [arrowhead=vee,color=gray50,style=dashed,label=\"2 steps elided\"];".to_string()));
+    }
+
+    #[test]
+    fn graphviz_keeps_filtered_nodes_of_linear_chains() {
+        let mut manager = make_manager_with_step_filter(Box::new(BlockStepTo(1)));
+        let statements = run_graphviz_collapsing_chains(&mut manager, "filtered_chains");
+        assert!(statements.iter().any(|statement| statement.starts_with("_n2 [")));
+        assert!(!statements.iter().any(|statement| statement.contains("elided")));
+    }
+
+    // 0 → 1 → 2 → [0, 3] : with memo, the step from 2 back to the root closes a cycle through it
+    struct CycleConf;
+
+    impl AbstractProcessConfiguration for CycleConf {
+        type ContextAndParameterization = ();
+        type DomainSpecificNode = Node;
+        type DomainSpecificStep = Step;
+        type Priorities = FlatPriorities;
+        type MutablePersistentState = ();
+        type FiltrationResult = ();
+
+        fn process_new_step(_ctx: &(), _state: &mut (), _parent: &Node, step: &Step) -> Node {
+            Node(step.0)
+        }
+
+        fn collect_next_steps(_ctx: &(), _state: &(), parent: &Node) -> Vec<Step> {
+            match parent.0 {
+                0 => vec![Step(1)],
+                1 => vec![Step(2)],
+                2 => vec![Step(0), Step(3)],
+                _ => vec![],
+            }
+        }
+    }
+
+    impl AbstractProcessMutablePersistentState<CycleConf> for () {
+        fn get_initial_state(_ctx: &(), _initial: &Node) -> Self {}
+    }
+
+    // draws the nodes of CycleConf as LabelDrawer does
+    struct CycleDrawer;
+
+    impl GraphVizProcessDrawer<CycleConf> for CycleDrawer {
+        fn draw_node_view(&self, ctx: &(), node: &Node, view_index: usize, path: &Path) -> BuiltinGraphvizLoggerItemStyle {
+            LabelDrawer.draw_node_view(ctx, node, view_index, path)
+        }
+        fn draw_step(&self, ctx: &(), step: &Step, path: &Path) -> BuiltinGraphvizLoggerItemStyle {
+            LabelDrawer.draw_step(ctx, step, path)
+        }
+        fn step_edge_color(&self, _ctx: &(), _step: &Step) -> GraphvizColor { GraphvizColor::black }
+        fn draw_filter(&self, ctx: &(), result: &(), path: &Path) -> BuiltinGraphvizLoggerItemStyle {
+            LabelDrawer.draw_filter(ctx, result, path)
+        }
+        fn filter_edge_color(&self, _ctx: &(), _result: &()) -> GraphvizColor { GraphvizColor::red }
+    }
+
+    #[test]
+    fn graphviz_never_collapses_the_root_of_a_cycle() {
+        // with memo : node 0 (id 1) → node 1 (id 2) → node 2 (id 3) → [node 0, node 3 (id 4)]
+        let mut logger = make_graphviz_logger_drawing_with("root_cycle", Box::new(CycleDrawer));
+        logger.configuration.collapse_linear_chains = true;
+        let mut manager = GenericProcessManager::new(
+            (),
+            QueueSearchStrategy::BFS,
+            GenericProcessPriorities::new(FlatPriorities, false),
+            GenericFiltersManager::default(),
+            true,
+            Node(0),
+        );
+        let mut loggers = LoggerRegistry::new();
+        let graphviz = loggers.add(logger);
+        let _ = loggers.drive(&mut manager);
+        let graph = loggers.take_product(graphviz).unwrap();
+        let statements: Vec<String> = graph.items.iter().map(|item| item.to_dot_string())
+            .chain(graph.edges.iter().map(|edge| edge.to_dot_string()))
+            .collect();
+
+        // the root has a single parent and a single child, but is kept, only node 1 is elided
+        assert!(statements.iter().any(|statement| statement.starts_with("_n1 [")));
+        assert!(!statements.iter().any(|statement| statement.starts_with("_n2 [")));
+        assert!(statements.contains(&"_n1->_n3 [arrowhead=vee,color=gray50,style=dashed,label=\"2 steps elided\"];".to_string()));
+        assert!(statements.iter().any(|statement| statement.starts_with("_s_3_1->_n1 [")));
+        assert_eq!(statements.iter().filter(|statement| statement.contains("elided")).count(), 1);
+    }
+
+    fn run_graphviz_with_limits(limits: GraphVizOutputLimits, name: &str) -> Vec<String> {
+        let mut logger = make_graphviz_logger(name);
+        logger.configuration.limits = limits;
+        let mut manager = make_manager(false);
+        let mut loggers = LoggerRegistry::new();
+        let graphviz = loggers.add(logger);
+        let _ = loggers.drive(&mut manager);
+        let graph = loggers.take_product(graphviz).unwrap();
+        graph.items.iter().map(|item| item.to_dot_string()).chain(graph.edges.iter().map(|edge| edge.to_dot_string())).collect()
+    }
+
+    #[test]
+    fn graphviz_summarizes_the_children_beyond_the_limit() {
+        // without memo : node 0 (id 1) has two children, the second one (id 3) is not drawn
+        let statements = run_graphviz_with_limits(
+            GraphVizOutputLimits { max_children: Some(1), ..Default::default() },
+            "truncated_children",
+        );
+        assert!(!statements.iter().any(|statement| statement.contains("_n3") || statement.contains("_s_1_3")));
+        assert!(statements.iter().any(|statement| statement.starts_with("_t1 [label=\"+1 more child\"")));
+        assert!(statements.contains(&"_n1->_t1 [arrowhead=vee,color=gray50,style=dashed];".to_string()));
+        assert!(statements.iter().any(|statement| statement.contains("1 nodes, 1 steps and 0 filtrations not shown")));
+    }
+
+    #[test]
+    fn graphviz_does_not_draw_the_nodes_beyond_the_depth_limit() {
+        let statements = run_graphviz_with_limits(
+            GraphVizOutputLimits { max_depth: Some(0), ..Default::default() },
+            "truncated_depth",
+        );
+        let nodes: Vec<&String> = statements.iter().filter(|statement| statement.starts_with("_n")).collect();
+        assert_eq!(nodes.len(), 2);
+        assert!(nodes[0].starts_with("_n1 [") && nodes[1].starts_with("_n1->_t1 "));
+        assert!(statements.iter().any(|statement| statement.starts_with("_t1 [label=\"+2 more children\"")));
+    }
+
+    // marks the nodes of a given value as witness targets
+    struct WitnessDrawer(u8);
+
+    impl GraphVizProcessDrawer<TestConf> for WitnessDrawer {
+        fn draw_node_view(&self, ctx: &(), node: &Node, view_index: usize, path: &Path) -> BuiltinGraphvizLoggerItemStyle {
+            LabelDrawer.draw_node_view(ctx, node, view_index, path)
+        }
+        fn draw_step(&self, ctx: &(), step: &Step, path: &Path) -> BuiltinGraphvizLoggerItemStyle {
+            LabelDrawer.draw_step(ctx, step, path)
+        }
+        fn step_edge_color(&self, _ctx: &(), _step: &Step) -> GraphvizColor { GraphvizColor::black }
+        fn draw_filter(&self, ctx: &(), result: &(), path: &Path) -> BuiltinGraphvizLoggerItemStyle {
+            LabelDrawer.draw_filter(ctx, result, path)
+        }
+        fn filter_edge_color(&self, _ctx: &(), _result: &()) -> GraphvizColor { GraphvizColor::red }
+        fn is_witness_target(&self, _ctx: &(), node: &Node) -> bool { node.0 == self.0 }
+    }
+
+    #[test]
+    fn graphviz_highlights_the_shortest_witness_path() {
+        // with memo : node 1 (id 3) is reached from node 0 (id 1) and from node 2 (id 2)
+        let mut logger = make_graphviz_logger("witness");
+        logger.drawer = Box::new(WitnessDrawer(1));
+        let mut manager = make_manager(true);
+        let mut loggers = LoggerRegistry::new();
+        let graphviz = loggers.add(logger);
+        let _ = loggers.drive(&mut manager);
+        let graph = loggers.take_product(graphviz).unwrap();
+        let statements: Vec<String> = graph.items.iter().map(|item| item.to_dot_string())
+            .chain(graph.edges.iter().map(|edge| edge.to_dot_string()))
+            .collect();
+        let highlighted: Vec<&String> = statements.iter().filter(|statement| statement.contains("red")).collect();
+        assert_eq!(highlighted.len(), 5);
+        for prefix in ["_n1 [", "_s_1_3 [", "_n3 [", "_n1->_s_1_3 ", "_s_1_3->_n3 "] {
+            assert!(highlighted.iter().any(|statement| statement.starts_with(prefix)), "{} is not highlighted", prefix);
+        }
+        assert!(statements.contains(&"_s_1_3->_n3 [arrowhead=vee,color=red,style=bold];".to_string()));
+    }
+
+    #[test]
+    fn graphviz_restates_the_witness_nodes_when_streaming() {
+        let mut logger = make_graphviz_logger("witness_streaming");
+        logger.drawer = Box::new(WitnessDrawer(1));
+        logger.configuration.streaming = true;
+        let dot_path = logger.get_dot_file_path();
+        let mut manager = make_manager(true);
+        let mut loggers = LoggerRegistry::new();
+        loggers.add(logger);
+        let _ = loggers.drive(&mut manager);
+        let dot = std::fs::read_to_string(&dot_path).unwrap();
+        for node_id in ["_n1", "_n3", "_s_1_3"] {
+            assert!(dot.contains(&format!("\n\t{} [color=red,penwidth=3];", node_id)));
+        }
+        assert!(!dot.contains("_n2 [color=red"));
+        // the graph is strict, hence restating the edges restyles them
+        assert!(dot.starts_with("strict digraph G {"));
+        assert!(dot.contains("\n\t_n1->_s_1_3 [color=red,style=bold];"));
+        assert!(dot.contains("\n\t_s_1_3->_n3 [color=red,style=bold];"));
+        assert!(!dot.contains("_n1->_s_1_2 [color=red"));
+    }
+
+    // decorates the nodes with a color per exploration status
+    struct StatusDrawer;
+
+    impl GraphVizProcessDrawer<TestConf> for StatusDrawer {
+        fn draw_node_view(&self, ctx: &(), node: &Node, view_index: usize, path: &Path) -> BuiltinGraphvizLoggerItemStyle {
+            LabelDrawer.draw_node_view(ctx, node, view_index, path)
+        }
+        fn draw_step(&self, ctx: &(), step: &Step, path: &Path) -> BuiltinGraphvizLoggerItemStyle {
+            LabelDrawer.draw_step(ctx, step, path)
+        }
+        fn step_edge_color(&self, _ctx: &(), _step: &Step) -> GraphvizColor { GraphvizColor::black }
+        fn draw_filter(&self, ctx: &(), result: &(), path: &Path) -> BuiltinGraphvizLoggerItemStyle {
+            LabelDrawer.draw_filter(ctx, result, path)
+        }
+        fn filter_edge_color(&self, _ctx: &(), _result: &()) -> GraphvizColor { GraphvizColor::red }
+        fn node_status_decoration(&self, status: GraphVizNodeExplorationStatus) -> Option<Vec<GraphvizNodeStyleItem>> {
+            let color = match status {
+                GraphVizNodeExplorationStatus::Leaf => GraphvizColor::green,
+                GraphVizNodeExplorationStatus::Expanded => GraphvizColor::blue,
+                GraphVizNodeExplorationStatus::Frontier => GraphvizColor::orange,
+            };
+            Some(vec![GraphvizNodeStyleItem::Color(color)])
+        }
+    }
+
+    fn run_graphviz_decorating_nodes(stop_on_node: Option<u32>, name: &str) -> Vec<String> {
+        let mut logger = make_graphviz_logger(name);
+        logger.drawer = Box::new(StatusDrawer);
+        let mut manager = make_manager(true);
+        let mut loggers = LoggerRegistry::new();
+        let graphviz = loggers.add(logger);
+        if let Some(node_id) = stop_on_node {
+            loggers.add(ControlOnNode(node_id, LoggerControl::Stop));
+        }
+        let _ = loggers.drive(&mut manager);
+        let graph = loggers.take_product(graphviz).unwrap();
+        graph.items.iter().map(|item| item.to_dot_string()).filter(|statement| statement.starts_with("_n")).collect()
+    }
+
+    #[test]
+    fn graphviz_decorates_nodes_according_to_their_exploration_status() {
+        let completed = run_graphviz_decorating_nodes(None, "status_completed");
+        assert_eq!(completed.len(), 3);
+        assert!(completed[0].starts_with("_n1 [") && completed[0].ends_with(",color=blue];"));
+        assert!(completed[1].starts_with("_n2 [") && completed[1].ends_with(",color=blue];"));
+        assert!(completed[2].starts_with("_n3 [") && completed[2].ends_with(",color=green];"));
+
+        // the run is cut short before any node is expanded
+        let stopped = run_graphviz_decorating_nodes(Some(3), "status_stopped");
+        assert_eq!(stopped.len(), 3);
+        assert!(stopped.iter().all(|statement| statement.ends_with(",color=orange];")));
+    }
+
+    #[test]
+    fn graphviz_draws_memoization_cross_edges_apart_from_tree_edges() {
+        // with memo : node 1 (id 3) is discovered from node 0 (id 1), the step from node 2 (id 2) is a cross-edge
+        let mut logger = make_graphviz_logger("edge_kinds");
+        logger.configuration.step_format = GraphVizLoggerStepFormat::EdgeLabel;
+        let mut manager = make_manager(true);
+        let mut loggers = LoggerRegistry::new();
+        let graphviz = loggers.add(logger);
+        let _ = loggers.drive(&mut manager);
+        let edges: Vec<String> = loggers.take_product(graphviz).unwrap()
+            .edges.iter().map(|edge| edge.to_dot_string()).collect();
+        assert_eq!(edges, vec![
+            "_n1->_n2 [arrowhead=vee,color=black,label=\"to 2\",fontcolor=black,fontsize=12];".to_string(),
+            "_n1->_n3 [arrowhead=vee,color=black,label=\"to 1\",fontcolor=black,fontsize=12];".to_string(),
+            "_n2->_n3 [arrowhead=vee,color=black,style=dotted,constraint=false,label=\"to 1\",fontcolor=black,fontsize=12];".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn graphviz_annotates_nodes_with_their_discovery_index_and_depth() {
+        let mut logger = make_graphviz_logger("discovery");
+        logger.configuration.annotate_discovery = true;
+        logger.configuration.align_depths = true;
+        let dot_path = logger.get_dot_file_path();
+        let mut manager = make_manager(true);
+        let mut loggers = LoggerRegistry::new();
+        let graphviz = loggers.add(logger);
+        let _ = loggers.drive(&mut manager);
+        let dot = std::fs::read_to_string(&dot_path).unwrap();
+        // the drawn labels are kept, the annotations restate the nodes
+        assert!(dot.contains("_n3 [shape=rectangle,label=\"node 1\""));
+        let annotations = "\n\t_n1 [xlabel=\"#0, depth 0\"];\n\t_n2 [xlabel=\"#1, depth 1\"];\n\t_n3 [xlabel=\"#2, depth 1\"];\n\t{rank=same; _n2; _n3};\n";
+        assert!(dot.contains(annotations));
+        // the product has them as well
+        assert!(loggers.take_product(graphviz).unwrap().to_dot_string().contains(annotations));
+    }
+
+    #[test]
+    fn graphviz_colours_nodes_according_to_the_heatmap_metric() {
+        // with memo : node 0 (id 1) discovered node 2 (id 2) and node 1 (id 3)
+        let mut logger = make_graphviz_logger("heatmap");
+        logger.configuration.heatmap = Some(GraphVizHeatmapOptions::new(GraphVizNodeMetric::SubtreeSize));
+        let mut manager = make_manager(true);
+        let mut loggers = LoggerRegistry::new();
+        let graphviz = loggers.add(logger);
+        let _ = loggers.drive(&mut manager);
+        let graph = loggers.take_product(graphviz).unwrap();
+        let statements: Vec<String> = graph.items.iter().map(|item| item.to_dot_string()).collect();
+        assert!(statements[0].starts_with("_n1 [") && statements[0].ends_with(";fillcolor=red3,style=\"filled\"];"));
+        assert!(statements[1].starts_with("_n2 [") && statements[1].ends_with(";fillcolor=lightyellow,style=\"filled\"];"));
+        assert!(statements[3].starts_with("_n3 [") && statements[3].ends_with(";fillcolor=lightyellow,style=\"filled\"];"));
+
+        let key = statements.iter().find(|statement| statement.starts_with("subgraph cluster__heatmap {")).unwrap();
+        assert!(key.contains("label=\"heatmap : subtree size\""));
+        assert_eq!(key.matches("\t_heat").count(), 9);
+        assert!(key.contains("\t_heat0 [label=\"≥ 1\",shape=rectangle,style=filled;fillcolor=lightyellow,style=\"filled\"];"));
+    }
+
+    // dashes the leaves
+    struct DashedLeavesDrawer;
+
+    impl GraphVizProcessDrawer<TestConf> for DashedLeavesDrawer {
+        fn draw_node_view(&self, ctx: &(), node: &Node, view_index: usize, path: &Path) -> BuiltinGraphvizLoggerItemStyle {
+            LabelDrawer.draw_node_view(ctx, node, view_index, path)
+        }
+        fn draw_step(&self, ctx: &(), step: &Step, path: &Path) -> BuiltinGraphvizLoggerItemStyle {
+            LabelDrawer.draw_step(ctx, step, path)
+        }
+        fn step_edge_color(&self, _ctx: &(), _step: &Step) -> GraphvizColor { GraphvizColor::black }
+        fn draw_filter(&self, ctx: &(), result: &(), path: &Path) -> BuiltinGraphvizLoggerItemStyle {
+            LabelDrawer.draw_filter(ctx, result, path)
+        }
+        fn filter_edge_color(&self, _ctx: &(), _result: &()) -> GraphvizColor { GraphvizColor::red }
+        fn node_status_decoration(&self, status: GraphVizNodeExplorationStatus) -> Option<Vec<GraphvizNodeStyleItem>> {
+            match status {
+                GraphVizNodeExplorationStatus::Leaf => Some(vec![GraphvizNodeStyleItem::Style(vec![GvNodeStyleKind::Dashed])]),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn graphviz_heatmap_keeps_the_style_of_the_nodes() {
+        // with memo : node 1 (id 3) is the only leaf
+        let mut logger = make_graphviz_logger("heatmap_dashed");
+        logger.drawer = Box::new(DashedLeavesDrawer);
+        logger.configuration.heatmap = Some(GraphVizHeatmapOptions::new(GraphVizNodeMetric::SubtreeSize));
+        let mut manager = make_manager(true);
+        let mut loggers = LoggerRegistry::new();
+        let graphviz = loggers.add(logger);
+        let _ = loggers.drive(&mut manager);
+        let graph = loggers.take_product(graphviz).unwrap();
+        let statements: Vec<String> = graph.items.iter().map(|item| item.to_dot_string()).collect();
+        assert!(statements[0].starts_with("_n1 [") && statements[0].ends_with(";fillcolor=red3,style=\"filled\"];"));
+        assert!(statements[3].starts_with("_n3 [") && statements[3].ends_with(";fillcolor=lightyellow,style=\"filled,dashed\"];"));
+
+        // when streaming, the restated styles add to the streamed ones
+        let mut logger = make_graphviz_logger("heatmap_dashed_streamed");
+        logger.drawer = Box::new(DashedLeavesDrawer);
+        logger.configuration.heatmap = Some(GraphVizHeatmapOptions::new(GraphVizNodeMetric::SubtreeSize));
+        logger.configuration.streaming = true;
+        let dot_path = logger.get_dot_file_path();
+        let mut manager = make_manager(true);
+        let mut loggers = LoggerRegistry::new();
+        loggers.add(logger);
+        let _ = loggers.drive(&mut manager);
+        let dot = std::fs::read_to_string(&dot_path).unwrap();
+        assert!(dot.contains("\n\t_n3 [style=filled;fillcolor=lightyellow,style=\"filled\"];"));
+        assert!(dot.contains("\n\t_n3 [style=\"filled,dashed\"];"));
+    }
+    // draws every node as two image views, hence as an anchored cluster
+    struct ImageNodeDrawer;
+
+    impl GraphVizProcessDrawer<TestConf> for ImageNodeDrawer {
+        fn node_view_count(&self) -> usize { 2 }
+        fn draw_node_view(&self, _ctx: &(), _node: &Node, _view_index: usize, _path: &Path) -> BuiltinGraphvizLoggerItemStyle {
+            BuiltinGraphvizLoggerItemStyle::CustomImage
+        }
+        fn draw_step(&self, ctx: &(), step: &Step, path: &Path) -> BuiltinGraphvizLoggerItemStyle {
+            LabelDrawer.draw_step(ctx, step, path)
+        }
+        fn step_edge_color(&self, _ctx: &(), _step: &Step) -> GraphvizColor { GraphvizColor::black }
+        fn draw_filter(&self, ctx: &(), result: &(), path: &Path) -> BuiltinGraphvizLoggerItemStyle {
+            LabelDrawer.draw_filter(ctx, result, path)
+        }
+        fn filter_edge_color(&self, _ctx: &(), _result: &()) -> GraphvizColor { GraphvizColor::red }
+    }
+
+    #[test]
+    fn graphviz_heatmap_fills_the_clusters_of_image_drawers() {
+        let mut logger = make_graphviz_logger("heatmap_clusters");
+        logger.drawer = Box::new(ImageNodeDrawer);
+        logger.configuration.heatmap = Some(GraphVizHeatmapOptions::new(GraphVizNodeMetric::SubtreeSize));
+        let mut manager = make_manager(true);
+        let mut loggers = LoggerRegistry::new();
+        let graphviz = loggers.add(logger);
+        let _ = loggers.drive(&mut manager);
+        let graph = loggers.take_product(graphviz).unwrap();
+        let statements: Vec<String> = graph.items.iter().map(|item| item.to_dot_string()).collect();
+        // the heatmap fill comes after the default fill of the clusters
+        assert!(statements[0].starts_with("subgraph cluster__n1 {\n"));
+        assert!(statements[0].contains("style=filled;fillcolor=red3;\nstyle=\"filled\";\n\t_n1_drawn0 ["));
+        assert!(statements[1].contains("style=filled;fillcolor=lightyellow;\nstyle=\"filled\";\n\t_n2_drawn0 ["));
+    }
+
+    // draws node 2 as an anchored cluster within phase 7
+    struct PhasedImageNodeDrawer;
+
+    impl GraphVizProcessDrawer<TestConf> for PhasedImageNodeDrawer {
+        fn node_view_count(&self) -> usize { 2 }
+        fn draw_node_view(&self, ctx: &(), node: &Node, view_index: usize, path: &Path) -> BuiltinGraphvizLoggerItemStyle {
+            ImageNodeDrawer.draw_node_view(ctx, node, view_index, path)
+        }
+        fn draw_step(&self, ctx: &(), step: &Step, path: &Path) -> BuiltinGraphvizLoggerItemStyle {
+            LabelDrawer.draw_step(ctx, step, path)
+        }
+        fn step_edge_color(&self, _ctx: &(), _step: &Step) -> GraphvizColor { GraphvizColor::black }
+        fn draw_filter(&self, ctx: &(), result: &(), path: &Path) -> BuiltinGraphvizLoggerItemStyle {
+            LabelDrawer.draw_filter(ctx, result, path)
+        }
+        fn filter_edge_color(&self, _ctx: &(), _result: &()) -> GraphvizColor { GraphvizColor::red }
+        fn node_phase(&self, _ctx: &(), node: &Node) -> Option<usize> { (node.0 == 2).then_some(7) }
+    }
+
+    fn first_frame_highlighting_anchored_clusters(name: &str, streaming: bool) -> String {
+        let mut logger = make_graphviz_logger(name);
+        logger.drawer = Box::new(PhasedImageNodeDrawer);
+        logger.configuration.streaming = streaming;
+        let mut options = GraphVizSnapshotOptions::every_events(3);
+        options.render = false;
+        logger.configuration.snapshots = Some(options);
+        let frames_folder = logger.get_frames_folder();
+        let mut manager = make_manager(true);
+        let mut loggers = LoggerRegistry::new();
+        loggers.add(logger);
+        let _ = loggers.drive(&mut manager);
+        // taken after NewNode 1, NewNode 2 and NewStep 1→2
+        std::fs::read_to_string(frames_folder.join("frame_00000.dot")).unwrap()
+    }
+
+    #[test]
+    fn graphviz_snapshots_highlight_anchored_clusters_within_their_phase() {
+        let first = first_frame_highlighting_anchored_clusters("snapshots_clusters", false);
+        assert_eq!(first.matches("subgraph cluster__n2 {").count(), 1);
+        assert!(first.contains("\tsubgraph cluster__n2 {\nstyle=filled;fillcolor=lightgrey;\nlabel=\"\";\ncolor=red;\npenwidth=3;\n"));
+        let phase = &first[first.find("subgraph cluster_phase7 {").unwrap()..];
+        assert!(phase.contains("subgraph cluster__n2 {"));
+
+        // when streaming, the cluster is restated within the subgraph of its phase
+        let streamed = first_frame_highlighting_anchored_clusters("snapshots_clusters_streaming", true);
+        assert!(streamed.ends_with(
+            "\n\tsubgraph cluster_phase7 {\n\tsubgraph cluster__n2 {\ncolor=red;\npenwidth=3;\n}\n}\n}"
+        ));
+        assert_eq!(streamed.matches("subgraph cluster__n2 {").count(), 2);
+    }
+
+    #[test]
+    fn graphviz_annotates_anchored_clusters_within_their_phase() {
+        let mut logger = make_graphviz_logger("discovery_clusters");
+        logger.drawer = Box::new(PhasedImageNodeDrawer);
+        logger.configuration.annotate_discovery = true;
+        logger.configuration.align_depths = true;
+        let mut manager = make_manager(true);
+        let mut loggers = LoggerRegistry::new();
+        let graphviz = loggers.add(logger);
+        let _ = loggers.drive(&mut manager);
+        let statements: Vec<String> = loggers.take_product(graphviz).unwrap()
+            .items.iter().map(|item| item.to_dot_string()).collect();
+        // node 2 (id 2) is annotated within phase 7, the other nodes at the top level
+        let phase = statements.iter().find(|statement| statement.starts_with("subgraph cluster_phase7 {")).unwrap();
+        assert!(phase.contains("\tsubgraph cluster__n2 {\nlabel=\"#1, depth 1\";\n}"));
+        assert!(statements.contains(&"subgraph cluster__n1 {\nlabel=\"#0, depth 0\";\n}".to_string()));
+        assert!(statements.contains(&"subgraph cluster__n3 {\nlabel=\"#2, depth 1\";\n}".to_string()));
+        assert_eq!(statements.iter().filter(|statement| statement.starts_with("subgraph cluster__n2 {")).count(), 0);
+        // nodes 1 and 2 are at the same depth but in different phases, hence not aligned
+        assert!(statements.iter().all(|statement| !statement.contains("rank=same")));
+        assert!(!phase.contains("rank=same"));
+    }
+}